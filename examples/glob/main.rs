@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 use std::path::Path;
 
-use config::{Config, File};
+use config::{Config, Directory, DirectoryOrder, File};
 use glob::glob;
 
 fn main() {
@@ -64,4 +64,21 @@ fn main() {
             .try_deserialize::<HashMap<String, String>>()
             .unwrap()
     );
+
+    // Option 4
+    // --------
+    // Gather all conf files from conf/ in the order of their numeric prefix, with the format
+    // detected from their extension.
+    let settings = Config::builder()
+        .add_source(Directory::new("examples/glob/conf").order(DirectoryOrder::Numeric))
+        .build()
+        .unwrap();
+
+    // Print out our settings (as a HashMap)
+    println!(
+        "\n{:?} \n\n-----------",
+        settings
+            .try_deserialize::<HashMap<String, String>>()
+            .unwrap()
+    );
 }
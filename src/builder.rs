@@ -1,9 +1,13 @@
 use std::str::FromStr;
 
 use crate::error::Result;
+use crate::layer::{Layer, LayeredCache};
 use crate::map::Map;
+use crate::merge::{ArrayMerge, ArrayMerges, DELETE};
+use crate::profile::Profiles;
 #[cfg(feature = "async")]
 use crate::source::AsyncSource;
+use crate::source::Mounted;
 use crate::{config::Config, path::Expression, source::Source, value::Value};
 
 /// A configuration builder
@@ -97,9 +101,19 @@ use crate::{config::Config, path::Expression, source::Source, value::Value};
 pub struct ConfigBuilder<St: BuilderState> {
     defaults: Map<Expression, Value>,
     overrides: Map<Expression, Value>,
+    options: Options,
     state: St,
 }
 
+/// How a [`ConfigBuilder`] builds the configuration out of the values it collected.
+#[derive(Debug, Clone, Default)]
+struct Options {
+    interpolate: bool,
+    track_layers: bool,
+    arrays: ArrayMerges,
+    profiles: Profiles,
+}
+
 /// Represents [`ConfigBuilder`] state.
 pub trait BuilderState {}
 
@@ -162,6 +176,36 @@ impl<St: BuilderState> ConfigBuilder<St> {
         Ok(self)
     }
 
+    /// Removes `key`, with everything under it, whatever the defaults and sources set
+    ///
+    /// ```rust
+    /// # use config::*;
+    /// # fn main() -> Result<(), ConfigError> {
+    /// let config = Config::builder()
+    ///     .set_default("server.port", 8080)?
+    ///     .set_default("server.tls.cert", "cert.pem")?
+    ///     .unset("server.tls")?
+    ///     .build()?;
+    ///
+    /// assert!(config.get_table("server")?.get("tls").is_none());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Sources delete keys by setting them to [`DELETE`].
+    ///
+    /// # Errors
+    ///
+    /// Fails if `Expression::from_str(key)` fails.
+    pub fn unset<S>(mut self, key: S) -> Result<Self>
+    where
+        S: AsRef<str>,
+    {
+        self.overrides
+            .insert(Expression::from_str(key.as_ref())?, DELETE.into());
+        Ok(self)
+    }
+
     /// Sets an override if value is Some(_)
     ///
     /// This function sets an overwrite value if Some(_) is passed. If None is passed, this function does nothing.
@@ -181,6 +225,140 @@ impl<St: BuilderState> ConfigBuilder<St> {
         }
         Ok(self)
     }
+
+    /// Expands references in string values once all layers are merged.
+    ///
+    /// - `${key.path}` is replaced by the value at that key of the merged configuration
+    /// - `${env:NAME}` is replaced by the environment variable `NAME`
+    /// - `${reference:-default}` falls back to `default` if the reference is not set
+    /// - `$${` stands for a literal `${`
+    ///
+    /// A string made of a single reference takes the referenced value, whatever its type.
+    /// Building fails if a reference is not set and has no default, or if references form a cycle.
+    ///
+    /// ```rust
+    /// # use config::*;
+    /// # fn main() -> Result<(), ConfigError> {
+    /// let config = Config::builder()
+    ///     .set_default("db.host", "localhost")?
+    ///     .set_default("db.port", 5432)?
+    ///     .set_default("db.url", "postgres://${db.host}:${db.port}/${env:DB_NAME:-app}")?
+    ///     .interpolate(true)
+    ///     .build()?;
+    ///
+    /// assert_eq!(config.get_string("db.url")?, "postgres://localhost:5432/app");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn interpolate(mut self, enabled: bool) -> Self {
+        self.options.interpolate = enabled;
+        self
+    }
+
+    /// Keeps the values of each layer (defaults, each source and overrides) in the built
+    /// configuration, so that [`Config::explain`] can tell where the value of a key came from.
+    ///
+    /// This is off by default, as the configuration then holds every value once per layer
+    /// setting it on top of the merged value.
+    pub fn track_layers(mut self, enabled: bool) -> Self {
+        self.options.track_layers = enabled;
+        self
+    }
+
+    /// Selects the profile `name` in the sources.
+    ///
+    /// Sources may then hold a `default` table and a `profile` table with a table per profile.
+    /// The `default` table of each source is merged over the rest of it, then the table of the
+    /// profile, before the source is merged over the previous ones.
+    ///
+    /// ```toml
+    /// [default]
+    /// log.level = "info"
+    /// server.port = 8080
+    ///
+    /// [profile.dev]
+    /// log.level = "debug"
+    ///
+    /// [profile.prod]
+    /// server.port = 80
+    /// ```
+    ///
+    /// ```rust
+    /// # use config::*;
+    /// # fn main() -> Result<(), ConfigError> {
+    /// # #[cfg(feature = "json")]
+    /// # {
+    /// # let settings = r#"{"default": {"log": {"level": "info"}}, "profile": {"dev": {"log": {"level": "debug"}}}}"#;
+    /// let config = Config::builder()
+    ///     .add_source(File::from_str(settings, FileFormat::Json))
+    ///     .profile("dev")
+    ///     .build()?;
+    ///
+    /// assert_eq!(config.get_string("log.level")?, "debug");
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn profile(mut self, name: &str) -> Self {
+        self.options.profiles.enabled = true;
+        self.options.profiles.name = Some(name.into());
+        self
+    }
+
+    /// Selects the profile named by the environment variable `var`, such as `APP_PROFILE`, see
+    /// [`profile`](Self::profile).
+    ///
+    /// The variable is read when building. If it is not set, the profile set with
+    /// [`profile`](Self::profile) is selected, or none, in which case only the `default` tables
+    /// are merged.
+    pub fn profile_env(mut self, var: &str) -> Self {
+        self.options.profiles.enabled = true;
+        self.options.profiles.env = Some(var.into());
+        self
+    }
+
+    /// Sets how the arrays of each source combine with the arrays set before it at the same key,
+    /// [`ArrayMerge::Replace`] by default.
+    ///
+    /// Defaults are set before the sources, overrides replace the arrays whatever the strategy.
+    pub fn array_merge(mut self, strategy: ArrayMerge) -> Self {
+        self.options.arrays.default = strategy;
+        self
+    }
+
+    /// Sets how the arrays at `key` combine, instead of the strategy of
+    /// [`array_merge`](Self::array_merge).
+    ///
+    /// ```rust
+    /// # use config::*;
+    /// # fn main() -> Result<(), ConfigError> {
+    /// # #[cfg(feature = "json")]
+    /// # {
+    /// let config = Config::builder()
+    ///     .add_source(File::from_str(
+    ///         r#"{"servers": [{"name": "a", "port": 80}, {"name": "b", "port": 81}]}"#,
+    ///         FileFormat::Json,
+    ///     ))
+    ///     .add_source(File::from_str(
+    ///         r#"{"servers": [{"name": "b", "port": 8081}, {"name": "c", "port": 82}]}"#,
+    ///         FileFormat::Json,
+    ///     ))
+    ///     .array_merge_at("servers", ArrayMerge::ByKey("name".into()))
+    ///     .build()?;
+    ///
+    /// assert_eq!(config.get_int("servers[1].port")?, 8081);
+    /// assert_eq!(config.get_string("servers[2].name")?, "c");
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// The key is written as for [`Config::get`](crate::Config::get), arrays nested in arrays
+    /// include the index, for instance `servers[0].ports`.
+    pub fn array_merge_at(mut self, key: &str, strategy: ArrayMerge) -> Self {
+        self.options.arrays.keys.insert(key.into(), strategy);
+        self
+    }
 }
 
 impl ConfigBuilder<DefaultState> {
@@ -197,6 +375,41 @@ impl ConfigBuilder<DefaultState> {
         self
     }
 
+    /// Registers new [`Source`] in this builder, nesting its properties under `prefix`.
+    ///
+    /// The source does not need to know where its values end up, for instance a standalone
+    /// `db.yaml` can be mounted at `database`:
+    ///
+    /// ```rust
+    /// # use config::*;
+    /// # fn main() -> Result<(), ConfigError> {
+    /// # #[cfg(feature = "toml")]
+    /// # {
+    /// let config = Config::builder()
+    ///     .add_source_at("database", File::from_str("host = 'localhost'", FileFormat::Toml))?
+    ///     .build()?;
+    ///
+    /// assert_eq!(config.get_string("database.host")?, "localhost");
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Calling this method does not invoke any I/O. [`Source`] is only saved in internal register for later use.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `Expression::from_str(prefix)` fails.
+    pub fn add_source_at<T>(self, prefix: &str, source: T) -> Result<Self>
+    where
+        T: Source + Send + Sync + 'static,
+    {
+        Ok(self.add_source(Mounted {
+            prefix: Expression::from_str(prefix)?,
+            source: Box::new(source) as Box<dyn Source + Send + Sync>,
+        }))
+    }
+
     /// Registers new [`AsyncSource`] in this builder and forces transition to [`AsyncState`].
     ///
     /// Calling this method does not invoke any I/O. [`AsyncSource`] is only saved in internal register for later use.
@@ -216,11 +429,35 @@ impl ConfigBuilder<DefaultState> {
             },
             defaults: self.defaults,
             overrides: self.overrides,
+            options: self.options,
         };
 
         async_state.add_async_source(source)
     }
 
+    /// Registers new [`AsyncSource`] in this builder, nesting its properties under `prefix`, and
+    /// forces transition to [`AsyncState`], see [`add_source_at`](Self::add_source_at).
+    ///
+    /// Calling this method does not invoke any I/O. [`AsyncSource`] is only saved in internal register for later use.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `Expression::from_str(prefix)` fails.
+    #[cfg(feature = "async")]
+    pub fn add_async_source_at<T>(
+        self,
+        prefix: &str,
+        source: T,
+    ) -> Result<ConfigBuilder<AsyncState>>
+    where
+        T: AsyncSource + Send + Sync + 'static,
+    {
+        Ok(self.add_async_source(Mounted {
+            prefix: Expression::from_str(prefix)?,
+            source: Box::new(source) as Box<dyn AsyncSource + Send + Sync>,
+        }))
+    }
+
     /// Reads all registered [`Source`]s.
     ///
     /// This is the method that invokes all I/O operations.
@@ -230,7 +467,12 @@ impl ConfigBuilder<DefaultState> {
     /// If source collection fails, be it technical reasons or related to inability to read data as `Config` for different reasons,
     /// this method returns error.
     pub fn build(self) -> Result<Config> {
-        Self::build_internal(self.defaults, self.overrides, &self.state.sources)
+        Self::build_internal(
+            self.defaults,
+            self.overrides,
+            &self.state.sources,
+            &self.options,
+        )
     }
 
     /// Reads all registered [`Source`]s.
@@ -246,27 +488,45 @@ impl ConfigBuilder<DefaultState> {
             self.defaults.clone(),
             self.overrides.clone(),
             &self.state.sources,
+            &self.options,
         )
     }
 
+    /// Lists the files and directories the sources are read from, see [`Source::watch_paths`].
+    #[cfg(feature = "watch")]
+    pub(crate) fn watch_paths(&self) -> Vec<std::path::PathBuf> {
+        self.state
+            .sources
+            .iter()
+            .flat_map(|source| source.watch_paths())
+            .collect()
+    }
+
     fn build_internal(
         defaults: Map<Expression, Value>,
         overrides: Map<Expression, Value>,
         sources: &[Box<dyn Source + Send + Sync>],
+        options: &Options,
     ) -> Result<Config> {
-        let mut cache: Value = Map::<String, Value>::new().into();
+        let mut cache = LayeredCache::with_options(
+            options.arrays.clone(),
+            options.profiles.select(),
+            options.track_layers,
+        );
 
         // Add defaults
-        for (key, val) in defaults {
-            key.set(&mut cache, val);
-        }
+        cache.set_values(Layer::Default, &defaults);
 
         // Add sources
-        sources.collect_to(&mut cache)?;
+        for (index, source) in sources.iter().enumerate() {
+            cache.collect_source(index, source.as_ref())?;
+        }
 
         // Add overrides
-        for (key, val) in overrides {
-            key.set(&mut cache, val);
+        cache.set_values(Layer::Override, &overrides);
+
+        if options.interpolate {
+            cache.interpolate()?;
         }
 
         Ok(Config::new(cache))
@@ -287,6 +547,24 @@ impl ConfigBuilder<AsyncState> {
         self
     }
 
+    /// Registers new [`Source`] in this builder, nesting its properties under `prefix`, see
+    /// [`add_source_at`](ConfigBuilder::<DefaultState>::add_source_at).
+    ///
+    /// Calling this method does not invoke any I/O. [`Source`] is only saved in internal register for later use.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `Expression::from_str(prefix)` fails.
+    pub fn add_source_at<T>(self, prefix: &str, source: T) -> Result<Self>
+    where
+        T: Source + Send + Sync + 'static,
+    {
+        Ok(self.add_source(Mounted {
+            prefix: Expression::from_str(prefix)?,
+            source: Box::new(source) as Box<dyn Source + Send + Sync>,
+        }))
+    }
+
     /// Registers new [`AsyncSource`] in this builder.
     ///
     /// Calling this method does not invoke any I/O. [`AsyncSource`] is only saved in internal register for later use.
@@ -299,6 +577,25 @@ impl ConfigBuilder<AsyncState> {
         self
     }
 
+    /// Registers new [`AsyncSource`] in this builder, nesting its properties under `prefix`, see
+    /// [`add_source_at`](ConfigBuilder::<DefaultState>::add_source_at).
+    ///
+    /// Calling this method does not invoke any I/O. [`AsyncSource`] is only saved in internal register for later use.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `Expression::from_str(prefix)` fails.
+    #[cfg(feature = "async")]
+    pub fn add_async_source_at<T>(self, prefix: &str, source: T) -> Result<Self>
+    where
+        T: AsyncSource + Send + Sync + 'static,
+    {
+        Ok(self.add_async_source(Mounted {
+            prefix: Expression::from_str(prefix)?,
+            source: Box::new(source) as Box<dyn AsyncSource + Send + Sync>,
+        }))
+    }
+
     /// Reads all registered defaults, [`Source`]s, [`AsyncSource`]s and overrides.
     ///
     /// This is the method that invokes all I/O operations.
@@ -308,7 +605,13 @@ impl ConfigBuilder<AsyncState> {
     /// If source collection fails, be it technical reasons or related to inability to read data as `Config` for different reasons,
     /// this method returns error.
     pub async fn build(self) -> Result<Config> {
-        Self::build_internal(self.defaults, self.overrides, &self.state.sources).await
+        Self::build_internal(
+            self.defaults,
+            self.overrides,
+            &self.state.sources,
+            &self.options,
+        )
+        .await
     }
 
     /// Reads all registered defaults, [`Source`]s, [`AsyncSource`]s and overrides.
@@ -324,6 +627,7 @@ impl ConfigBuilder<AsyncState> {
             self.defaults.clone(),
             self.overrides.clone(),
             &self.state.sources,
+            &self.options,
         )
         .await
     }
@@ -332,25 +636,38 @@ impl ConfigBuilder<AsyncState> {
         defaults: Map<Expression, Value>,
         overrides: Map<Expression, Value>,
         sources: &[SourceType],
+        options: &Options,
     ) -> Result<Config> {
-        let mut cache: Value = Map::<String, Value>::new().into();
+        let mut cache = LayeredCache::with_options(
+            options.arrays.clone(),
+            options.profiles.select(),
+            options.track_layers,
+        );
 
         // Add defaults
-        for (key, val) in defaults {
-            key.set(&mut cache, val);
-        }
+        cache.set_values(Layer::Default, &defaults);
 
-        for source in sources.iter() {
+        for (index, source) in sources.iter().enumerate() {
             match source {
-                SourceType::Sync(source) => source.collect_to(&mut cache)?,
+                SourceType::Sync(source) => cache.collect_source(index, source.as_ref())?,
                 #[cfg(feature = "async")]
-                SourceType::Async(source) => source.collect_to(&mut cache).await?,
+                SourceType::Async(source) => {
+                    let mut tree: Value = Map::<String, Value>::new().into();
+                    source.collect_to(&mut tree).await?;
+                    let layer = Layer::Source {
+                        index,
+                        description: source.describe(),
+                    };
+                    cache.merge(layer, tree);
+                }
             }
         }
 
         // Add overrides
-        for (key, val) in overrides {
-            key.set(&mut cache, val);
+        cache.set_values(Layer::Override, &overrides);
+
+        if options.interpolate {
+            cache.interpolate()?;
         }
 
         Ok(Config::new(cache))
@@ -1,14 +1,23 @@
 use std::fmt::Debug;
+use std::fs;
+use std::io;
+use std::path::Path;
 
 use serde::de::Deserialize;
-use serde::ser::Serialize;
+use serde::ser::{Serialize, Serializer};
 
 use crate::builder::{ConfigBuilder, DefaultState};
+use crate::de::{self, UnusedKey};
+use crate::diff::Diff;
 use crate::error::{ConfigError, Result};
+use crate::file::FileFormat;
+use crate::format::FormatWriter;
+use crate::layer::{Explanation, Layer, LayeredCache};
 use crate::map::Map;
 use crate::path;
 use crate::ser::ConfigSerializer;
 use crate::source::Source;
+use crate::suggest;
 use crate::value::{Table, Value};
 
 /// A prioritized configuration repository. It maintains a set of
@@ -19,6 +28,8 @@ pub struct Config {
     defaults: Map<path::Expression, Value>,
     overrides: Map<path::Expression, Value>,
     sources: Vec<Box<dyn Source + Send + Sync>>,
+    /// The values of each layer, kept with [`ConfigBuilder::track_layers`].
+    layers: Option<Vec<(Layer, Value)>>,
 
     /// Root of the cached configuration.
     pub cache: Value,
@@ -30,15 +41,19 @@ impl Default for Config {
             defaults: Default::default(),
             overrides: Default::default(),
             sources: Default::default(),
+            layers: Default::default(),
             cache: Value::new(None, Table::new()),
         }
     }
 }
 
 impl Config {
-    pub(crate) fn new(value: Value) -> Self {
+    pub(crate) fn new(cache: LayeredCache) -> Self {
+        let (cache, layers) = cache.into_parts();
+
         Self {
-            cache: value,
+            layers,
+            cache,
             ..Self::default()
         }
     }
@@ -54,24 +69,20 @@ impl Config {
     /// Configuration is automatically refreshed after a mutation
     /// operation (`set`, `merge`, `set_default`, etc.).
     fn refresh(&mut self) -> Result<&mut Self> {
-        self.cache = {
-            let mut cache: Value = Map::<String, Value>::new().into();
+        let mut cache = LayeredCache::new(self.layers.is_some());
 
-            // Add defaults
-            for (key, val) in &self.defaults {
-                key.set(&mut cache, val.clone());
-            }
+        // Add defaults
+        cache.set_values(Layer::Default, &self.defaults);
 
-            // Add sources
-            self.sources.collect_to(&mut cache)?;
+        // Add sources
+        for (index, source) in self.sources.iter().enumerate() {
+            cache.collect_source(index, source.as_ref())?;
+        }
 
-            // Add overrides
-            for (key, val) in &self.overrides {
-                key.set(&mut cache, val.clone());
-            }
+        // Add overrides
+        cache.set_values(Layer::Override, &self.overrides);
 
-            cache
-        };
+        (self.cache, self.layers) = cache.into_parts();
 
         Ok(self)
     }
@@ -100,7 +111,7 @@ impl Config {
         // Traverse the cache using the path to (possibly) retrieve a value
         let value = expr.get(&self.cache).cloned();
 
-        value.ok_or_else(|| ConfigError::NotFound(key.into()))
+        value.ok_or_else(|| ConfigError::NotFound(key.into(), self.suggest_keys(key)))
     }
 
     pub fn get<'de, T: Deserialize<'de>>(&self, key: &str) -> Result<T> {
@@ -140,11 +151,214 @@ impl Config {
             .and_then(|value| value.into_array().map_err(|e| e.extend_with_key(key)))
     }
 
+    /// Lists the keys close to `key`, closest first, to suggest in place of a key that is not set.
+    ///
+    /// These are the suggestions of the [`ConfigError::NotFound`] returned for `key`.
+    ///
+    /// ```rust
+    /// # use config::*;
+    /// # fn main() -> Result<(), ConfigError> {
+    /// let config = Config::builder()
+    ///     .set_default("database.url", "postgres://localhost")?
+    ///     .build()?;
+    ///
+    /// assert_eq!(
+    ///     config.get_string("databse.url").unwrap_err().to_string(),
+    ///     r#"configuration property "databse.url" not found, did you mean "database.url"?"#
+    /// );
+    /// assert_eq!(config.suggest_keys("databse.url"), ["database.url"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn suggest_keys(&self, key: &str) -> Vec<String> {
+        let paths = suggest::key_paths(&self.cache);
+        suggest::suggest(key, paths.iter().map(String::as_str))
+    }
+
+    /// Explains where the value of `key` came from.
+    ///
+    /// The returned [`Explanation`] lists the value every layer (defaults, each source and overrides)
+    /// supplied for the key, the winning one and the ones it shadowed. The configuration must be
+    /// built with [`ConfigBuilder::track_layers`].
+    ///
+    /// ```rust
+    /// # use config::*;
+    /// # fn main() -> Result<(), ConfigError> {
+    /// let config = Config::builder()
+    ///     .set_default("db.port", 5432)?
+    ///     .set_override("db.port", 5433)?
+    ///     .track_layers(true)
+    ///     .build()?;
+    ///
+    /// let explanation = config.explain("db.port")?;
+    /// assert_eq!(explanation.winner().unwrap().layer(), &Layer::Override);
+    /// assert_eq!(explanation.shadowed()[0].layer(), &Layer::Default);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Fails if `key` is not a valid path, if it is not set or if the layers were not tracked.
+    pub fn explain(&self, key: &str) -> Result<Explanation> {
+        let layers = self.layers.as_ref().ok_or_else(|| {
+            ConfigError::Message(
+                "explaining keys requires building with `ConfigBuilder::track_layers`".to_owned(),
+            )
+        })?;
+        let expr: path::Expression = key.parse()?;
+        let value = expr
+            .clone()
+            .get(&self.cache)
+            .cloned()
+            .ok_or_else(|| ConfigError::NotFound(key.into(), self.suggest_keys(key)))?;
+
+        Ok(Explanation::new(key, value, &expr, layers))
+    }
+
+    /// Compares this configuration with a newer one, listing the keys `other` adds, removes or
+    /// changes.
+    ///
+    /// ```rust
+    /// # use config::*;
+    /// # fn main() -> Result<(), ConfigError> {
+    /// let current = Config::builder()
+    ///     .set_default("server.port", 8080)?
+    ///     .set_default("server.debug", true)?
+    ///     .build()?;
+    /// let next = Config::builder()
+    ///     .set_default("server.port", 9090)?
+    ///     .build()?;
+    ///
+    /// let diff = current.diff(&next);
+    /// assert_eq!(
+    ///     diff.to_string(),
+    ///     "- server.debug = true\n~ server.port = 8080 -> 9090"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn diff(&self, other: &Self) -> Diff {
+        Diff::between("", &self.cache, &other.cache)
+    }
+
     /// Attempt to deserialize the entire configuration into the requested type.
     pub fn try_deserialize<'de, T: Deserialize<'de>>(self) -> Result<T> {
         T::deserialize(self)
     }
 
+    /// Attempt to deserialize the entire configuration into the requested type, reporting every
+    /// problem at once.
+    ///
+    /// Unlike [`Config::try_deserialize`], this does not stop at the first missing key or value
+    /// of the wrong type: it goes through the whole configuration and returns them all, each with
+    /// its key and origin, in a [`ConfigError::Multiple`].
+    ///
+    /// ```rust
+    /// # use config::*;
+    /// # use serde_derive::Deserialize;
+    /// #[derive(Debug, Deserialize)]
+    /// struct Settings {
+    ///     port: u16,
+    ///     host: String,
+    ///     debug: bool,
+    /// }
+    ///
+    /// # fn main() -> Result<(), ConfigError> {
+    /// let config = Config::builder()
+    ///     .set_default("port", "eighty")?
+    ///     .set_default("debug", true)?
+    ///     .build()?;
+    ///
+    /// match config.try_deserialize_collecting_errors::<Settings>() {
+    ///     Err(ConfigError::Multiple(errors)) => assert_eq!(errors.len(), 2),
+    ///     res => panic!("unexpected {res:?}"),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn try_deserialize_collecting_errors<'de, T: Deserialize<'de>>(self) -> Result<T> {
+        de::deserialize_collecting_errors(self.cache)
+    }
+
+    /// Attempt to deserialize the entire configuration into the requested type, also listing the
+    /// keys it did not read.
+    ///
+    /// Keys the type has no field for are silently skipped by [`Config::try_deserialize`], so a
+    /// misspelled `server.prot` in a file or `APP_SERVER__PROT` in the environment goes unnoticed.
+    /// Each [`UnusedKey`] tells the path of such a key and where its value came from. Keys under
+    /// a `#[serde(flatten)]` field are not tracked.
+    ///
+    /// ```rust
+    /// # use config::*;
+    /// # use serde_derive::Deserialize;
+    /// #[derive(Debug, Deserialize)]
+    /// struct Server {
+    ///     port: u16,
+    /// }
+    ///
+    /// #[derive(Debug, Deserialize)]
+    /// struct Settings {
+    ///     server: Server,
+    /// }
+    ///
+    /// # fn main() -> Result<(), ConfigError> {
+    /// let config = Config::builder()
+    ///     .set_default("server.port", 8080)?
+    ///     .set_override("server.prot", 80)?
+    ///     .build()?;
+    ///
+    /// let (settings, unused) = config.try_deserialize_reporting_unused::<Settings>()?;
+    /// assert_eq!(settings.server.port, 8080);
+    /// assert_eq!(unused[0].key(), "server.prot");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn try_deserialize_reporting_unused<'de, T: Deserialize<'de>>(
+        self,
+    ) -> Result<(T, Vec<UnusedKey>)> {
+        de::deserialize_reporting_unused(self.cache)
+    }
+
+    /// Renders the entire configuration as text of the given format.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the configuration holds values the format cannot represent,
+    /// for instance nested tables in INI.
+    pub fn to_string_as<F: FormatWriter>(&self, format: F) -> Result<String> {
+        let values = self.cache.clone().into_table()?;
+
+        format.render(&values).map_err(ConfigError::Foreign)
+    }
+
+    /// Writes the entire configuration to a file.
+    ///
+    /// The format is chosen by the extension of `path`, for instance `Settings.toml` is written as TOML.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the extension is not of a registered file format,
+    /// if rendering fails (see [`to_string_as`](Self::to_string_as)) or if the file cannot be written.
+    pub fn write_to_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        let format = path
+            .extension()
+            .and_then(|extension| FileFormat::from_extension(&extension.to_string_lossy()))
+            .ok_or_else(|| {
+                ConfigError::Foreign(Box::new(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "configuration file \"{}\" is not of a registered file format",
+                        path.to_string_lossy()
+                    ),
+                )))
+            })?;
+
+        let text = self.to_string_as(format)?;
+        fs::write(path, text).map_err(|err| ConfigError::Foreign(Box::new(err)))
+    }
+
     /// Attempt to serialize the entire configuration from the given type.
     pub fn try_from<T: Serialize>(from: &T) -> Result<Self> {
         let mut serializer = ConfigSerializer::default();
@@ -153,11 +367,24 @@ impl Config {
     }
 }
 
+impl Serialize for Config {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.cache.serialize(serializer)
+    }
+}
+
 impl Source for Config {
     fn clone_into_box(&self) -> Box<dyn Source + Send + Sync> {
         Box::new((*self).clone())
     }
 
+    fn describe(&self) -> String {
+        "configuration".to_owned()
+    }
+
     fn collect(&self) -> Result<Map<String, Value>> {
         self.cache.clone().into_table()
     }
@@ -1,6 +1,9 @@
-use std::collections::VecDeque;
+use std::cell::{Cell, RefCell};
+use std::collections::{HashSet, VecDeque};
 use std::convert::TryInto;
+use std::fmt;
 use std::iter::Enumerate;
+use std::mem;
 
 use serde::de;
 
@@ -11,24 +14,32 @@ use crate::value::{Table, Value, ValueKind};
 
 macro_rules! try_convert_number {
     (signed, $self:expr, $size:literal) => {{
-        let num = $self.into_int()?;
+        let value: Value = $self;
+        let origin = value.origin().map(ToOwned::to_owned);
+        let location = value.location();
+        let num = value.into_int()?;
         num.try_into().map_err(|_| {
             ConfigError::invalid_type(
-                None,
+                origin,
                 Unexpected::I64(num),
                 concat!("an signed ", $size, " bit integer"),
             )
+            .at(location)
         })?
     }};
 
     (unsigned, $self:expr, $size:literal) => {{
-        let num = $self.into_uint()?;
+        let value: Value = $self;
+        let origin = value.origin().map(ToOwned::to_owned);
+        let location = value.location();
+        let num = value.into_uint()?;
         num.try_into().map_err(|_| {
             ConfigError::invalid_type(
-                None,
+                origin,
                 Unexpected::U64(num),
                 concat!("an unsigned ", $size, " bit integer"),
             )
+            .at(location)
         })?
     }};
 }
@@ -164,13 +175,35 @@ impl<'de> de::Deserializer<'de> for Value {
         })
     }
 
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        let keys = table_keys(&self);
+        self.deserialize_any(visitor)
+            .map_err(|e| e.suggest_fields(&keys, fields))
+    }
+
     serde::forward_to_deserialize_any! {
         char seq
-        bytes byte_buf map struct unit
+        bytes byte_buf map unit
         identifier ignored_any unit_struct tuple_struct tuple
     }
 }
 
+/// The keys of `value` if it is a table, to suggest from when a field is missing.
+fn table_keys(value: &Value) -> Vec<String> {
+    match value.kind {
+        ValueKind::Table(ref table) => table.keys().cloned().collect(),
+        _ => Vec::new(),
+    }
+}
+
 struct StrDeserializer<'a>(&'a str);
 
 impl<'de> de::Deserializer<'de> for StrDeserializer<'_> {
@@ -416,3 +449,567 @@ impl<'de> de::Deserializer<'de> for Config {
         deserialize_struct(name: &'static str, fields: &'static [&'static str]);
     }
 }
+
+/// Deserializes `root`, collecting every invalid and missing value instead of stopping at the
+/// first one.
+///
+/// A value of the wrong type is recorded and replaced by the default of the requested type
+/// (`0`, `""`, `false`, nothing) so the walk carries on. Errors raised by the visitor itself,
+/// such as a missing field, abort the walk: they are recorded, the value they were raised for
+/// becomes a placeholder and the walk starts over, until it completes or stops making progress.
+pub(crate) fn deserialize_collecting_errors<'de, T: de::Deserialize<'de>>(
+    root: Value,
+) -> Result<T> {
+    let collected = Collected::default();
+
+    loop {
+        let result = T::deserialize(Collecting {
+            value: root.clone(),
+            path: Vec::new(),
+            collected: &collected,
+        });
+
+        match result {
+            Ok(value) if collected.errors.borrow().is_empty() => return Ok(value),
+            Ok(_) => break,
+            Err(error) => match collected.restart.take() {
+                Some(restart) => {
+                    if restart.record {
+                        collected.record(restart.path.clone(), error);
+                    }
+                    if !collected.placeholders.borrow_mut().insert(restart.path) {
+                        break;
+                    }
+                }
+                None => {
+                    collected.record(Vec::new(), error);
+                    break;
+                }
+            },
+        }
+    }
+
+    // Maps may not keep the order of their keys, report errors in the order of their paths
+    let mut errors = collected.errors.into_inner();
+    errors.sort_by(|(a, _), (b, _)| a.cmp(b));
+    Err(ConfigError::Multiple(
+        errors.into_iter().map(|(_, error)| error).collect(),
+    ))
+}
+
+/// Deserializes `root` like [`Config::try_deserialize`], also listing the keys the deserialized
+/// type did not read.
+pub(crate) fn deserialize_reporting_unused<'de, T: de::Deserialize<'de>>(
+    root: Value,
+) -> Result<(T, Vec<UnusedKey>)> {
+    let collected = Collected::default();
+    let result = T::deserialize(Collecting {
+        value: root.clone(),
+        path: Vec::new(),
+        collected: &collected,
+    });
+
+    let mut unused = collected.unused.into_inner();
+    unused.sort_by(|(a, _), (b, _)| a.cmp(b));
+    let unused = unused
+        .into_iter()
+        .map(|(path, value)| UnusedKey {
+            key: render_path(&path).unwrap_or_default(),
+            value,
+        })
+        .collect();
+
+    match result {
+        Ok(value) if collected.errors.into_inner().is_empty() => Ok((value, unused)),
+        // Invalid values were replaced to carry on, report the first as `try_deserialize` does
+        _ => T::deserialize(root).map(|value| (value, unused)),
+    }
+}
+
+/// A key of the configuration the deserialized type never read, see
+/// [`Config::try_deserialize_reporting_unused`].
+#[derive(Debug, Clone)]
+pub struct UnusedKey {
+    key: String,
+    value: Value,
+}
+
+impl UnusedKey {
+    /// The path of the key, as accepted by [`Config::get`].
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// The value of the key, which may be a table holding more keys.
+    pub fn value(&self) -> &Value {
+        &self.value
+    }
+
+    /// The original location of the value, for instance the file it was read from.
+    pub fn origin(&self) -> Option<&str> {
+        self.value.origin()
+    }
+}
+
+impl fmt::Display for UnusedKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.key)?;
+
+        if let Some(origin) = self.origin() {
+            write!(f, " in {origin}")?;
+
+            if let Some(location) = self.value.location() {
+                write!(f, ":{location}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A step of the path from the root of the configuration to a value.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+fn render_path(path: &[Segment]) -> Option<String> {
+    let mut rendered = String::new();
+    for segment in path {
+        match *segment {
+            Segment::Key(ref key) if rendered.is_empty() => rendered.push_str(key),
+            Segment::Key(ref key) => {
+                rendered.push('.');
+                rendered.push_str(key);
+            }
+            Segment::Index(index) => rendered.push_str(&format!("[{index}]")),
+        }
+    }
+    (!path.is_empty()).then_some(rendered)
+}
+
+/// Prefixes the key of `error`, and of its suggestions, with `path`.
+fn prepend_path(path: &[Segment], error: ConfigError) -> ConfigError {
+    path.iter()
+        .rev()
+        .fold(error, |error, segment| match *segment {
+            Segment::Key(ref key) => error.prepend_key(key),
+            Segment::Index(index) => error.prepend_index(index),
+        })
+}
+
+/// Where a walk was aborted.
+struct Restart {
+    path: Vec<Segment>,
+    record: bool,
+}
+
+/// State shared by the walks of [`deserialize_collecting_errors`] and [`deserialize_reporting_unused`].
+#[derive(Default)]
+struct Collected {
+    errors: RefCell<Vec<(Vec<Segment>, ConfigError)>>,
+    /// Paths deserialized as an empty value of the requested type.
+    placeholders: RefCell<HashSet<Vec<Segment>>>,
+    restart: Cell<Option<Restart>>,
+    /// Values the visitors skipped.
+    unused: RefCell<Vec<(Vec<Segment>, Value)>>,
+}
+
+impl Collected {
+    /// Keeps `error`, unless an error of its kind was already raised at `path`.
+    fn record(&self, path: Vec<Segment>, error: ConfigError) {
+        let mut errors = self.errors.borrow_mut();
+        let kind = mem::discriminant(&error);
+        if !errors
+            .iter()
+            .any(|(p, e)| *p == path && mem::discriminant(e) == kind)
+        {
+            errors.push((path, error));
+        }
+    }
+
+    fn is_placeholder(&self, path: &[Segment]) -> bool {
+        self.placeholders.borrow().contains(path)
+    }
+
+    /// Notes the first error of a walk, raised while deserializing the value at `path`.
+    ///
+    /// `mismatch` describes the value if it is not of the shape the visitor asked for, and is
+    /// recorded instead of the message of the visitor, which knows neither origin nor key.
+    fn catch<T>(
+        &self,
+        path: &[Segment],
+        result: Result<T>,
+        mismatch: Option<ConfigError>,
+    ) -> Result<T> {
+        let error = match result {
+            Err(error) => error,
+            ok => return ok,
+        };
+
+        let restart = self.restart.take();
+        if restart.is_some() {
+            self.restart.set(restart);
+            return Err(error);
+        }
+
+        // A missing field is looked for again as a placeholder, any other error replaces the value
+        let mut restart_path = path.to_vec();
+        if let ConfigError::MissingField { field, .. } = error {
+            restart_path.push(Segment::Key(field.to_owned()));
+        }
+
+        let key = render_path(path);
+        let error = match (error, mismatch) {
+            (ConfigError::Message(_), Some(mismatch)) => mismatch,
+            (ConfigError::Message(message), None) => match key {
+                Some(ref key) => ConfigError::Message(format!("{message} for key `{key}`")),
+                None => ConfigError::Message(message),
+            },
+            (error, _) => error,
+        };
+        let error = prepend_path(path, error);
+
+        self.restart.set(Some(Restart {
+            path: restart_path,
+            record: !self.is_placeholder(path),
+        }));
+        Err(error)
+    }
+}
+
+fn unexpected(kind: &ValueKind) -> Unexpected {
+    match *kind {
+        ValueKind::Nil => Unexpected::Unit,
+        ValueKind::Boolean(b) => Unexpected::Bool(b),
+        ValueKind::I64(i) => Unexpected::I64(i),
+        ValueKind::I128(i) => Unexpected::I128(i),
+        ValueKind::U64(i) => Unexpected::U64(i),
+        ValueKind::U128(i) => Unexpected::U128(i),
+        ValueKind::Float(f) => Unexpected::Float(f),
+        ValueKind::String(ref s) => Unexpected::Str(s.clone()),
+        ValueKind::Table(_) => Unexpected::Map,
+        ValueKind::Array(_) => Unexpected::Seq,
+    }
+}
+
+/// Deserializer keeping track of the path of its value, see [`Collected`].
+struct Collecting<'a> {
+    value: Value,
+    path: Vec<Segment>,
+    collected: &'a Collected,
+}
+
+impl Collecting<'_> {
+    fn is_placeholder(&self) -> bool {
+        self.collected.is_placeholder(&self.path)
+    }
+
+    /// Converts the value, recording the error and substituting a default if it cannot be.
+    fn convert<T: Default>(&self, convert: impl FnOnce(Value) -> Result<T>) -> T {
+        if self.is_placeholder() {
+            return T::default();
+        }
+
+        convert(self.value.clone()).unwrap_or_else(|error| {
+            let error = prepend_path(&self.path, error);
+            self.collected.record(self.path.clone(), error);
+            T::default()
+        })
+    }
+
+    fn mismatch(&self, matches: bool, expected: &'static str) -> Option<ConfigError> {
+        (!matches).then(|| {
+            self.value
+                .invalid_type(unexpected(&self.value.kind), expected)
+        })
+    }
+
+    fn visit<'de, V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        if self.is_placeholder() {
+            return visitor.visit_unit();
+        }
+
+        match self.value.kind {
+            ValueKind::Nil => visitor.visit_unit(),
+            ValueKind::I64(i) => visitor.visit_i64(i),
+            ValueKind::I128(i) => visitor.visit_i128(i),
+            ValueKind::U64(i) => visitor.visit_u64(i),
+            ValueKind::U128(i) => visitor.visit_u128(i),
+            ValueKind::Boolean(b) => visitor.visit_bool(b),
+            ValueKind::Float(f) => visitor.visit_f64(f),
+            ValueKind::String(s) => visitor.visit_string(s),
+            ValueKind::Array(values) => {
+                visitor.visit_seq(CollectingSeq::new(values, self.path, self.collected))
+            }
+            ValueKind::Table(map) => {
+                visitor.visit_map(CollectingMap::new(map, self.path, self.collected))
+            }
+        }
+    }
+
+    fn visit_seq<'de, V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        if self.is_placeholder() {
+            return visitor.visit_seq(CollectingSeq::new(Vec::new(), self.path, self.collected));
+        }
+        self.visit(visitor)
+    }
+
+    fn visit_map<'de, V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        if self.is_placeholder() {
+            return visitor.visit_map(CollectingMap::new(Map::new(), self.path, self.collected));
+        }
+        self.visit(visitor)
+    }
+}
+
+/// Define `$method`s, `deserialize_foo`, converting the value and catching the errors of the
+/// visitor
+macro_rules! collecting_deserialize_scalar { { $(
+    $method:ident => $visit:ident ( $value:ident ) $convert:expr ;
+)* } => { $(
+    #[inline]
+    fn $method<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let converted = self.convert(|$value| Ok($convert));
+        self.collected.catch(&self.path, visitor.$visit(converted), None)
+    }
+)* } }
+
+impl<'de> de::Deserializer<'de> for Collecting<'_> {
+    type Error = ConfigError;
+
+    fn deserialize_any<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let (collected, path) = (self.collected, self.path.clone());
+        collected.catch(&path, self.visit(visitor), None)
+    }
+
+    collecting_deserialize_scalar! {
+        deserialize_bool => visit_bool(value) value.into_bool()?;
+        deserialize_i8 => visit_i8(value) try_convert_number!(signed, value, "8");
+        deserialize_i16 => visit_i16(value) try_convert_number!(signed, value, "16");
+        deserialize_i32 => visit_i32(value) try_convert_number!(signed, value, "32");
+        deserialize_i64 => visit_i64(value) try_convert_number!(signed, value, "64");
+        deserialize_u8 => visit_u8(value) try_convert_number!(unsigned, value, "8");
+        deserialize_u16 => visit_u16(value) try_convert_number!(unsigned, value, "16");
+        deserialize_u32 => visit_u32(value) try_convert_number!(unsigned, value, "32");
+        deserialize_u64 => visit_u64(value) try_convert_number!(unsigned, value, "64");
+        deserialize_f32 => visit_f32(value) value.into_float()? as f32;
+        deserialize_f64 => visit_f64(value) value.into_float()?;
+        deserialize_str => visit_string(value) value.into_string()?;
+        deserialize_string => visit_string(value) value.into_string()?;
+    }
+
+    fn deserialize_option<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let (collected, path) = (self.collected, self.path.clone());
+        let result = match self.value.kind {
+            _ if self.is_placeholder() => visitor.visit_none(),
+            ValueKind::Nil => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        };
+        collected.catch(&path, result, None)
+    }
+
+    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        let (collected, path) = (self.collected, self.path.clone());
+        collected.catch(&path, visitor.visit_newtype_struct(self), None)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        // Stand in for the first variant, with a value fit for a unit or a struct variant
+        let value = match variants.first() {
+            Some(&variant) if self.is_placeholder() => {
+                let mut table = Map::new();
+                table.insert(
+                    variant.to_owned(),
+                    Value::new(None, Map::<String, Value>::new()),
+                );
+                Value::new(None, table)
+            }
+            _ => self.value,
+        };
+        let result = visitor.visit_enum(EnumAccess {
+            value,
+            name,
+            variants,
+        });
+        self.collected.catch(&self.path, result, None)
+    }
+
+    fn deserialize_seq<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let (collected, path) = (self.collected, self.path.clone());
+        let is_array = matches!(self.value.kind, ValueKind::Array(_));
+        let mismatch = self.mismatch(is_array || self.is_placeholder(), "an array");
+        collected.catch(&path, self.visit_seq(visitor), mismatch)
+    }
+
+    fn deserialize_tuple<V: de::Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let (collected, path) = (self.collected, self.path.clone());
+        let is_table = matches!(self.value.kind, ValueKind::Table(_));
+        let mismatch = self.mismatch(is_table || self.is_placeholder(), "a map");
+        collected.catch(&path, self.visit_map(visitor), mismatch)
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        let (collected, path) = (self.collected, self.path.clone());
+        let is_table = matches!(self.value.kind, ValueKind::Table(_));
+        let mismatch = self.mismatch(is_table || self.is_placeholder(), "a map");
+        let keys = table_keys(&self.value);
+        let result = self
+            .visit_map(visitor)
+            .map_err(|e| e.suggest_fields(&keys, fields));
+        collected.catch(&path, result, mismatch)
+    }
+
+    fn deserialize_ignored_any<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        if !self.is_placeholder() {
+            let unused = (self.path, self.value);
+            self.collected.unused.borrow_mut().push(unused);
+        }
+        visitor.visit_unit()
+    }
+
+    serde::forward_to_deserialize_any! {
+        char bytes byte_buf unit unit_struct identifier
+    }
+}
+
+struct CollectingSeq<'a> {
+    elements: Enumerate<::std::vec::IntoIter<Value>>,
+    path: Vec<Segment>,
+    collected: &'a Collected,
+}
+
+impl<'a> CollectingSeq<'a> {
+    fn new(elements: Vec<Value>, path: Vec<Segment>, collected: &'a Collected) -> Self {
+        Self {
+            elements: elements.into_iter().enumerate(),
+            path,
+            collected,
+        }
+    }
+}
+
+impl<'de> de::SeqAccess<'de> for CollectingSeq<'_> {
+    type Error = ConfigError;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        match self.elements.next() {
+            Some((idx, value)) => {
+                let mut path = self.path.clone();
+                path.push(Segment::Index(idx));
+                seed.deserialize(Collecting {
+                    value,
+                    path,
+                    collected: self.collected,
+                })
+                .map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        match self.elements.size_hint() {
+            (lower, Some(upper)) if lower == upper => Some(upper),
+            _ => None,
+        }
+    }
+}
+
+struct CollectingMap<'a> {
+    elements: VecDeque<(String, Value)>,
+    path: Vec<Segment>,
+    collected: &'a Collected,
+}
+
+impl<'a> CollectingMap<'a> {
+    fn new(table: Map<String, Value>, path: Vec<Segment>, collected: &'a Collected) -> Self {
+        let mut elements: VecDeque<_> = table.into_iter().collect();
+
+        // Fill in the fields found missing by previous walks
+        for placeholder in collected.placeholders.borrow().iter() {
+            if let Some((Segment::Key(key), parent)) = placeholder.split_last() {
+                if parent == path.as_slice() && elements.iter().all(|(k, _)| k != key) {
+                    elements.push_back((key.clone(), Value::new(None, ValueKind::Nil)));
+                }
+            }
+        }
+
+        Self {
+            elements,
+            path,
+            collected,
+        }
+    }
+}
+
+impl<'de> de::MapAccess<'de> for CollectingMap<'_> {
+    type Error = ConfigError;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>>
+    where
+        K: de::DeserializeSeed<'de>,
+    {
+        if let Some((ref key_s, _)) = self.elements.front() {
+            let key_de = Value::new(None, key_s as &str);
+            let key = de::DeserializeSeed::deserialize(seed, key_de)?;
+
+            Ok(Some(key))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        let (key, value) = self.elements.pop_front().unwrap();
+        let mut path = self.path.clone();
+        path.push(Segment::Key(key));
+        seed.deserialize(Collecting {
+            value,
+            path,
+            collected: self.collected,
+        })
+    }
+}
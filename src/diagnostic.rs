@@ -0,0 +1,177 @@
+use std::fmt;
+
+use crate::error::ConfigError;
+use crate::file::format;
+use crate::file::IncludeError;
+use crate::value::Location;
+
+/// Renders a [`ConfigError`] over several lines, quoting the offending line of the source.
+///
+/// Created by [`ConfigError::diagnostic`].
+///
+/// ```text
+/// error: invalid type: string "not a port", expected an integer
+///  --> config/Settings.toml:6:8
+///   |
+/// 6 | port = "not a port"
+///   |        ^^^^^^^^^^^^ expected an integer, found string "not a port"
+///   |
+///   = key: database.port
+/// ```
+///
+/// The source is kept with the values read from files, errors of values read otherwise
+/// (for instance from [`File::from_str`](crate::File::from_str)) are rendered without it.
+#[derive(Debug)]
+pub struct Diagnostic<'a> {
+    error: &'a ConfigError,
+}
+
+impl ConfigError {
+    /// Renders the error over several lines, see [`Diagnostic`].
+    pub fn diagnostic(&self) -> Diagnostic<'_> {
+        Diagnostic { error: self }
+    }
+}
+
+/// A position in a file to point at.
+struct Snippet<'a> {
+    path: &'a str,
+    location: &'a Location,
+    label: String,
+}
+
+impl fmt::Display for Diagnostic<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self.error {
+            ConfigError::Type {
+                ref origin,
+                ref location,
+                ref unexpected,
+                expected,
+                ref key,
+            } => {
+                write!(f, "error: invalid type: {unexpected}, expected {expected}")?;
+
+                let gutter = match (origin, location) {
+                    (Some(path), Some(location)) => render_snippet(
+                        f,
+                        &Snippet {
+                            path,
+                            location,
+                            label: format!("expected {expected}, found {unexpected}"),
+                        },
+                    )?,
+                    (Some(path), None) => {
+                        write!(f, "\n --> {path}")?;
+                        2
+                    }
+                    _ => 2,
+                };
+
+                if let Some(key) = key {
+                    write!(f, "\n{:gutter$}= key: {key}", "")?;
+                }
+
+                Ok(())
+            }
+
+            ConfigError::FileParse {
+                ref uri,
+                ref location,
+                ref cause,
+            } => {
+                if let Some(include) = cause.downcast_ref::<IncludeError>() {
+                    write!(f, "{}", include.error.diagnostic())?;
+                    match uri {
+                        Some(uri) => write!(f, "\n  = included in {uri}")?,
+                        None => write!(f, "\n  = included from a string source")?,
+                    }
+                    return Ok(());
+                }
+
+                let failure = location.as_ref().and_then(|location| {
+                    format::locate_error(cause.as_ref(), location.source().unwrap_or_default())
+                });
+
+                match (uri, location, failure) {
+                    (Some(path), Some(location), Some((_, message))) => {
+                        write!(f, "error: {message}")?;
+                        render_snippet(
+                            f,
+                            &Snippet {
+                                path,
+                                location,
+                                label: String::new(),
+                            },
+                        )?;
+                    }
+                    (Some(path), _, _) => write!(f, "error: {cause}\n --> {path}")?,
+                    (None, _, _) => write!(f, "error: {cause}")?,
+                }
+
+                Ok(())
+            }
+
+            ConfigError::Multiple(ref errors) => {
+                for (i, error) in errors.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, "\n\n")?;
+                    }
+                    write!(f, "{}", error.diagnostic())?;
+                }
+
+                Ok(())
+            }
+
+            ref error => write!(f, "error: {error}"),
+        }
+    }
+}
+
+/// Writes the location and, if the source was kept, the quoted line with a caret underline.
+///
+/// Returns the width of the gutter, to align further notes with.
+fn render_snippet(f: &mut fmt::Formatter<'_>, snippet: &Snippet<'_>) -> Result<usize, fmt::Error> {
+    let location = snippet.location;
+    let text = location.source();
+    let source = text.and_then(|text| text.lines().nth(location.line().saturating_sub(1)));
+    let number = location.line().to_string();
+    let gutter = number.len() + 1;
+
+    write!(
+        f,
+        "\n{:width$}--> {}:{}",
+        "",
+        snippet.path,
+        location,
+        width = gutter - 1
+    )?;
+
+    let Some(source) = source else {
+        return Ok(gutter);
+    };
+
+    // Keep tabs, so the caret lines up however wide they are displayed
+    let indent: String = source
+        .chars()
+        .take(location.column().saturating_sub(1))
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect();
+    let width = match (text, location.span()) {
+        (Some(text), Some(span)) => text
+            .get(span)
+            .and_then(|spanned| spanned.lines().next())
+            .map_or(1, |spanned| spanned.chars().count().max(1)),
+        _ => 1,
+    };
+
+    write!(f, "\n{:gutter$}|", "")?;
+    write!(f, "\n{number} | {source}")?;
+    write!(f, "\n{:gutter$}| {indent}{}", "", "^".repeat(width))?;
+    if !snippet.label.is_empty() {
+        write!(f, " {}", snippet.label)?;
+    }
+    write!(f, "\n{:gutter$}|", "")?;
+
+    Ok(gutter)
+}
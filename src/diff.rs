@@ -0,0 +1,272 @@
+use std::fmt;
+
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+use crate::value::{Value, ValueKind};
+
+/// The keys that differ between two configurations.
+///
+/// Created by [`Config::diff`](crate::Config::diff). Tables are compared key by key and arrays
+/// item by item, down to the values that differ, regardless of the files they come from.
+/// Scalars are compared as the text they convert to, the way [`Config::get`](crate::Config::get)
+/// reads them: `8080` from a file and `"8080"` from the environment are the same, while `nil`
+/// only equals `nil`.
+///
+/// It displays as one line per key, strings quoted:
+///
+/// ```text
+/// + server.tls = true (config/production.toml:4:7)
+/// - server.debug = false (config/default.toml:2:9)
+/// ~ server.port = 8080 -> 9090 (config/default.toml:3:8 -> config/production.toml:1:8)
+/// ```
+///
+/// It also serializes, for instance to JSON, as a list of objects with the `key`, the `change`
+/// (`added`, `removed` or `changed`) and the `old` and `new` values and their `origin`s.
+#[derive(Clone, Debug, Default)]
+pub struct Diff {
+    entries: Vec<DiffEntry>,
+}
+
+/// A key that differs between two configurations.
+#[derive(Clone, Debug)]
+pub struct DiffEntry {
+    key: String,
+    old: Option<Value>,
+    new: Option<Value>,
+}
+
+/// How a key differs between two configurations.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiffKind {
+    /// The key is only set in the new configuration.
+    Added,
+
+    /// The key is only set in the old configuration.
+    Removed,
+
+    /// The key is set to different values.
+    Changed,
+}
+
+impl Diff {
+    /// Compares the `old` and `new` values, which are at `key` (the root if empty).
+    pub(crate) fn between(key: &str, old: &Value, new: &Value) -> Self {
+        let mut entries = Vec::new();
+        walk(key, Some(old), Some(new), &mut entries);
+
+        Self { entries }
+    }
+
+    /// The keys that differ, in alphabetical order.
+    pub fn entries(&self) -> &[DiffEntry] {
+        &self.entries
+    }
+
+    /// Whether the configurations hold the same values.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Renders the differences as a JSON array, see [`Diff`].
+    #[cfg(feature = "json")]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("values serialize to JSON")
+    }
+}
+
+impl DiffEntry {
+    /// The path of the key, as accepted by [`Config::get`](crate::Config::get).
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// How the key differs.
+    pub fn kind(&self) -> DiffKind {
+        match (&self.old, &self.new) {
+            (None, _) => DiffKind::Added,
+            (_, None) => DiffKind::Removed,
+            _ => DiffKind::Changed,
+        }
+    }
+
+    /// The value in the old configuration, unless the key was added.
+    pub fn old_value(&self) -> Option<&Value> {
+        self.old.as_ref()
+    }
+
+    /// The value in the new configuration, unless the key was removed.
+    pub fn new_value(&self) -> Option<&Value> {
+        self.new.as_ref()
+    }
+}
+
+/// Adds the keys under `key` that differ between `old` and `new` to `entries`.
+fn walk(key: &str, old: Option<&Value>, new: Option<&Value>, entries: &mut Vec<DiffEntry>) {
+    let (old, new) = match (old, new) {
+        (Some(old), Some(new)) => (old, new),
+        (None, None) => return,
+        (old, new) => {
+            entries.push(DiffEntry {
+                key: key.to_owned(),
+                old: old.cloned(),
+                new: new.cloned(),
+            });
+            return;
+        }
+    };
+
+    match (&old.kind, &new.kind) {
+        (ValueKind::Table(old), ValueKind::Table(new)) => {
+            let mut keys: Vec<&String> = old.keys().chain(new.keys()).collect();
+            keys.sort();
+            keys.dedup();
+
+            for child in keys {
+                let path = if key.is_empty() {
+                    child.clone()
+                } else {
+                    format!("{key}.{child}")
+                };
+                walk(&path, old.get(child), new.get(child), entries);
+            }
+        }
+        (ValueKind::Array(old), ValueKind::Array(new)) => {
+            for index in 0..old.len().max(new.len()) {
+                walk(
+                    &format!("{key}[{index}]"),
+                    old.get(index),
+                    new.get(index),
+                    entries,
+                );
+            }
+        }
+        (a, b) if same_scalar(a, b) => {}
+        _ => entries.push(DiffEntry {
+            key: key.to_owned(),
+            old: Some(old.clone()),
+            new: Some(new.clone()),
+        }),
+    }
+}
+
+/// Whether the scalars `a` and `b` convert to the same values, see [`Diff`].
+fn same_scalar(a: &ValueKind, b: &ValueKind) -> bool {
+    match (a, b) {
+        (ValueKind::Nil, ValueKind::Nil) => true,
+        (ValueKind::Nil | ValueKind::Table(_) | ValueKind::Array(_), _)
+        | (_, ValueKind::Nil | ValueKind::Table(_) | ValueKind::Array(_)) => false,
+        _ => a == b || a.to_string() == b.to_string(),
+    }
+}
+
+/// Where a value was read from, with its position if known.
+fn origin(value: &Value) -> Option<String> {
+    let origin = value.origin()?;
+
+    Some(match value.location() {
+        Some(location) => format!("{origin}:{location}"),
+        None => origin.to_owned(),
+    })
+}
+
+impl fmt::Display for Diff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, entry) in self.entries.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{entry}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Displays a value, quoting strings so that `"nil"` or `"8080"` do not read like other values.
+struct Quoted<'a>(&'a Value);
+
+impl fmt::Display for Quoted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0.kind {
+            ValueKind::String(ref value) => write!(f, "{value:?}"),
+            _ => write!(f, "{}", self.0),
+        }
+    }
+}
+
+impl fmt::Display for DiffEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.old, &self.new) {
+            (Some(old), Some(new)) => {
+                write!(f, "~ {} = {} -> {}", self.key, Quoted(old), Quoted(new))?;
+                match (origin(old), origin(new)) {
+                    (Some(old), Some(new)) if old != new => write!(f, " ({old} -> {new})"),
+                    (None, Some(new)) => write!(f, " (-> {new})"),
+                    (Some(old), None) => write!(f, " ({old} ->)"),
+                    (Some(origin), Some(_)) => write!(f, " ({origin})"),
+                    (None, None) => Ok(()),
+                }
+            }
+            (None, Some(value)) | (Some(value), None) => {
+                let sign = if self.old.is_none() { '+' } else { '-' };
+                write!(f, "{sign} {} = {}", self.key, Quoted(value))?;
+                match origin(value) {
+                    Some(origin) => write!(f, " ({origin})"),
+                    None => Ok(()),
+                }
+            }
+            (None, None) => unreachable!(),
+        }
+    }
+}
+
+impl Serialize for Diff {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.entries.serialize(serializer)
+    }
+}
+
+impl Serialize for DiffEntry {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut entry = serializer.serialize_struct("DiffEntry", 4)?;
+        entry.serialize_field("key", &self.key)?;
+        entry.serialize_field("change", &self.kind())?;
+        entry.serialize_field("old", &self.old.as_ref().map(Side))?;
+        entry.serialize_field("new", &self.new.as_ref().map(Side))?;
+        entry.end()
+    }
+}
+
+impl Serialize for DiffKind {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(match *self {
+            Self::Added => "added",
+            Self::Removed => "removed",
+            Self::Changed => "changed",
+        })
+    }
+}
+
+/// A value of a [`DiffEntry`], serialized with its origin.
+struct Side<'a>(&'a Value);
+
+impl Serialize for Side<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut side = serializer.serialize_struct("Side", 2)?;
+        side.serialize_field("value", self.0)?;
+        side.serialize_field("origin", &origin(self.0))?;
+        side.end()
+    }
+}
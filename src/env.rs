@@ -1,9 +1,19 @@
 use std::env;
+#[cfg(feature = "dotenv")]
+use std::fs;
+#[cfg(feature = "dotenv")]
+use std::io;
+#[cfg(feature = "dotenv")]
+use std::path::PathBuf;
 
 #[cfg(feature = "convert-case")]
 use convert_case::{Case, Casing};
 
+#[cfg(feature = "dotenv")]
+use crate::error::ConfigError;
 use crate::error::Result;
+#[cfg(feature = "dotenv")]
+use crate::file::format::dotenv;
 use crate::map::Map;
 use crate::source::Source;
 use crate::value::{Value, ValueKind};
@@ -88,6 +98,10 @@ pub struct Environment {
     /// }
     /// ```
     source: Option<Map<String, String>>,
+
+    /// `.env` file read in place of the environment, see [`Environment::dotenv`].
+    #[cfg(feature = "dotenv")]
+    dotenv: Option<PathBuf>,
 }
 
 impl Environment {
@@ -217,6 +231,41 @@ impl Environment {
         self.source = source;
         self
     }
+
+    /// Reads the variables of the `.env` file at `path` rather than those of the environment,
+    /// with the same prefix, separator and parsing rules, so that a `.env.local` file can stand
+    /// in for the environment during development. If the file is missing, such as in production,
+    /// the variables of the environment are read instead.
+    ///
+    /// See [`FileFormat::Dotenv`](crate::FileFormat::Dotenv) for the syntax of the file.
+    #[cfg(feature = "dotenv")]
+    pub fn dotenv(mut self, path: impl Into<PathBuf>) -> Self {
+        self.dotenv = Some(path.into());
+        self
+    }
+
+    /// The path and the variables of the `.env` file, if one is read in place of the environment
+    /// and it exists.
+    #[cfg(feature = "dotenv")]
+    fn read_dotenv(&self) -> Result<Option<(String, Map<String, String>)>> {
+        let Some(ref path) = self.dotenv else {
+            return Ok(None);
+        };
+        let uri = path.to_string_lossy().into_owned();
+
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(error) => return Err(ConfigError::Foreign(Box::new(error))),
+        };
+        let variables = dotenv::parse_variables(&text)
+            .map_err(|cause| ConfigError::file_parse(Some(uri.clone()), &text, Box::new(cause)))?;
+
+        Ok(Some((
+            uri,
+            variables.into_iter().map(|v| (v.name, v.value)).collect(),
+        )))
+    }
 }
 
 impl Source for Environment {
@@ -224,8 +273,34 @@ impl Source for Environment {
         Box::new((*self).clone())
     }
 
+    fn describe(&self) -> String {
+        match self.prefix {
+            Some(ref prefix) => format!("environment with prefix \"{prefix}\""),
+            None => "environment".to_owned(),
+        }
+    }
+
+    #[cfg(feature = "dotenv")]
+    fn watch_paths(&self) -> Vec<PathBuf> {
+        match self.source {
+            Some(_) => Vec::new(),
+            None => self.dotenv.iter().cloned().collect(),
+        }
+    }
+
     fn collect(&self) -> Result<Map<String, Value>> {
         let mut m = Map::new();
+        #[cfg(feature = "dotenv")]
+        let dotenv = match self.source {
+            Some(_) => None,
+            None => self.read_dotenv()?,
+        };
+        #[cfg(feature = "dotenv")]
+        let uri: String = match dotenv {
+            Some((ref path, _)) => path.clone(),
+            None => "the environment".into(),
+        };
+        #[cfg(not(feature = "dotenv"))]
         let uri: String = "the environment".into();
 
         let separator = self.separator.as_deref().unwrap_or("");
@@ -275,33 +350,23 @@ impl Source for Environment {
             }
 
             let value = if self.try_parsing {
-                // convert to lowercase because bool parsing expects all lowercase
-                if let Ok(parsed) = value.to_lowercase().parse::<bool>() {
-                    ValueKind::Boolean(parsed)
-                } else if let Ok(parsed) = value.parse::<i64>() {
-                    ValueKind::I64(parsed)
-                } else if let Ok(parsed) = value.parse::<f64>() {
-                    ValueKind::Float(parsed)
-                } else if let Some(separator) = &self.list_separator {
-                    if let Some(keys) = &self.list_parse_keys {
-                        if keys.contains(&key) {
+                match parse_scalar(value) {
+                    ValueKind::String(value) => match self.list_separator {
+                        Some(ref separator)
+                            if self
+                                .list_parse_keys
+                                .as_ref()
+                                .map_or(true, |keys| keys.contains(&key)) =>
+                        {
                             let v: Vec<Value> = value
                                 .split(separator)
                                 .map(|s| Value::new(Some(&uri), ValueKind::String(s.to_owned())))
                                 .collect();
                             ValueKind::Array(v)
-                        } else {
-                            ValueKind::String(value)
                         }
-                    } else {
-                        let v: Vec<Value> = value
-                            .split(separator)
-                            .map(|s| Value::new(Some(&uri), ValueKind::String(s.to_owned())))
-                            .collect();
-                        ValueKind::Array(v)
-                    }
-                } else {
-                    ValueKind::String(value)
+                        _ => ValueKind::String(value),
+                    },
+                    parsed => parsed,
                 }
             } else {
                 ValueKind::String(value)
@@ -310,6 +375,12 @@ impl Source for Environment {
             m.insert(key, Value::new(Some(&uri), value));
         };
 
+        #[cfg(feature = "dotenv")]
+        if let Some((_, variables)) = dotenv {
+            variables.into_iter().for_each(collector);
+            return Ok(m);
+        }
+
         match &self.source {
             Some(source) => source.clone().into_iter().for_each(collector),
             None => env::vars().for_each(collector),
@@ -318,3 +389,17 @@ impl Source for Environment {
         Ok(m)
     }
 }
+
+/// Parses `value` as a boolean, an integer or a float, keeping it as a string otherwise.
+pub(crate) fn parse_scalar(value: String) -> ValueKind {
+    // convert to lowercase because bool parsing expects all lowercase
+    if let Ok(parsed) = value.to_lowercase().parse::<bool>() {
+        ValueKind::Boolean(parsed)
+    } else if let Ok(parsed) = value.parse::<i64>() {
+        ValueKind::I64(parsed)
+    } else if let Ok(parsed) = value.parse::<f64>() {
+        ValueKind::Float(parsed)
+    } else {
+        ValueKind::String(value)
+    }
+}
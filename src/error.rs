@@ -1,10 +1,16 @@
 use std::error::Error;
 use std::fmt;
 use std::result;
+#[cfg(feature = "diagnostics")]
+use std::sync::Arc;
 
 use serde::de;
 use serde::ser;
 
+use crate::file::format;
+use crate::suggest;
+use crate::value::Location;
+
 #[derive(Debug)]
 pub enum Unexpected {
     Bool(bool),
@@ -38,13 +44,16 @@ impl fmt::Display for Unexpected {
 
 /// Represents all possible errors that can occur when working with
 /// configuration.
+///
+/// Variants may be added in minor releases, matches need a wildcard arm.
 #[non_exhaustive]
 pub enum ConfigError {
     /// Configuration is frozen and no further mutations can be made.
     Frozen,
 
-    /// Configuration property was not found
-    NotFound(String),
+    /// Configuration property was not found, with the existing keys close to it, as listed by
+    /// [`Config::suggest_keys`](crate::Config::suggest_keys).
+    NotFound(String, Vec<String>),
 
     /// Configuration path could not be parsed.
     PathParse { cause: Box<dyn Error + Send + Sync> },
@@ -55,6 +64,9 @@ pub enum ConfigError {
         /// Example: `/path/to/config.json`
         uri: Option<String>,
 
+        /// Where the parser failed in the file, if its format reports it.
+        location: Option<Location>,
+
         /// The captured error from attempting to parse the file in its desired format.
         /// This is the actual error object from the library used for the parsing.
         cause: Box<dyn Error + Send + Sync>,
@@ -67,6 +79,9 @@ pub enum ConfigError {
         // TODO: Why is this called Origin but FileParse has a uri field?
         origin: Option<String>,
 
+        /// Where the value was written in the source, if known.
+        location: Option<Location>,
+
         /// What we found when parsing the value
         unexpected: Unexpected,
 
@@ -78,6 +93,39 @@ pub enum ConfigError {
         key: Option<String>,
     },
 
+    /// A field required by the type being deserialized is not set.
+    #[non_exhaustive]
+    MissingField {
+        /// The name of the field.
+        field: &'static str,
+
+        /// The key of the table the field is missing from, if it is not the root.
+        key: Option<String>,
+
+        /// The keys of the table close to the name of the field, which may be misspelled.
+        suggestions: Vec<String>,
+    },
+
+    /// A key is not a field of the type being deserialized, and the type does not allow it.
+    #[non_exhaustive]
+    UnknownField {
+        /// The name of the key.
+        field: String,
+
+        /// The fields of the type.
+        expected: &'static [&'static str],
+
+        /// The key of the table holding the unknown key, if it is not the root.
+        key: Option<String>,
+
+        /// The fields close to the key, which may be misspelled.
+        suggestions: Vec<String>,
+    },
+
+    /// Several errors, collected by
+    /// [`Config::try_deserialize_collecting_errors`](crate::Config::try_deserialize_collecting_errors).
+    Multiple(Vec<ConfigError>),
+
     /// Custom message
     Message(String),
 
@@ -95,18 +143,64 @@ impl ConfigError {
     ) -> Self {
         Self::Type {
             origin,
+            location: None,
             unexpected,
             expected,
             key: None,
         }
     }
 
+    /// Suggests the keys of a table, besides the `fields` of the struct deserialized from it,
+    /// for a field of that struct found missing.
+    #[must_use]
+    pub(crate) fn suggest_fields(mut self, keys: &[String], fields: &[&str]) -> Self {
+        if let Self::MissingField {
+            field,
+            key: None,
+            ref mut suggestions,
+        } = self
+        {
+            let keys = keys.iter().map(String::as_str);
+            *suggestions = suggest::suggest(field, keys.filter(|key| !fields.contains(key)));
+        }
+        self
+    }
+
+    /// A [`ConfigError::FileParse`] for a parser failing on `text`.
+    pub(crate) fn file_parse(
+        uri: Option<String>,
+        text: &str,
+        cause: Box<dyn Error + Send + Sync>,
+    ) -> Self {
+        let location = format::locate_error(cause.as_ref(), text).map(|(location, _)| location);
+        #[cfg(feature = "diagnostics")]
+        let location = location.map(|location| location.with_source(&Arc::new(text.to_owned())));
+
+        Self::FileParse {
+            uri,
+            location,
+            cause,
+        }
+    }
+
+    #[must_use]
+    pub(crate) fn at(mut self, at: Option<Location>) -> Self {
+        if let Self::Type {
+            ref mut location, ..
+        } = self
+        {
+            *location = at;
+        }
+        self
+    }
+
     // Have a proper error fire if the root of a file is ever not a Table
     // TODO: for now only json5 checked, need to finish others
     #[doc(hidden)]
     pub fn invalid_root(origin: Option<&String>, unexpected: Unexpected) -> Box<Self> {
         Box::new(Self::Type {
             origin: origin.cloned(),
+            location: None,
             unexpected,
             expected: "a map",
             key: None,
@@ -116,22 +210,16 @@ impl ConfigError {
     // FIXME: pub(crate)
     #[doc(hidden)]
     #[must_use]
-    pub fn extend_with_key(self, key: &str) -> Self {
+    pub fn extend_with_key(mut self, key: &str) -> Self {
         match self {
-            Self::Type {
-                origin,
-                unexpected,
-                expected,
-                ..
-            } => Self::Type {
-                origin,
-                unexpected,
-                expected,
-                key: Some(key.into()),
-            },
-
-            _ => self,
+            Self::Type { key: ref mut k, .. } => {
+                *k = Some(key.into());
+            }
+            // The key names the table the field belongs to, not the field itself
+            Self::MissingField { .. } | Self::UnknownField { .. } => return self.prepend_key(key),
+            _ => {}
         }
+        self
     }
 
     #[must_use]
@@ -148,16 +236,41 @@ impl ConfigError {
         match self {
             Self::Type {
                 origin,
+                location,
                 unexpected,
                 expected,
                 key,
             } => Self::Type {
                 origin,
+                location,
                 unexpected,
                 expected,
                 key: Some(concat(key)),
             },
-            Self::NotFound(key) => Self::NotFound(concat(Some(key))),
+            Self::NotFound(key, suggestions) => Self::NotFound(
+                concat(Some(key)),
+                suggestions.into_iter().map(|s| concat(Some(s))).collect(),
+            ),
+            Self::MissingField {
+                field,
+                key,
+                suggestions,
+            } => Self::MissingField {
+                field,
+                key: Some(concat(key)),
+                suggestions: suggestions.into_iter().map(|s| concat(Some(s))).collect(),
+            },
+            Self::UnknownField {
+                field,
+                expected,
+                key,
+                suggestions,
+            } => Self::UnknownField {
+                field,
+                expected,
+                key: Some(concat(key)),
+                suggestions: suggestions.into_iter().map(|s| concat(Some(s))).collect(),
+            },
             _ => self,
         }
     }
@@ -173,6 +286,28 @@ impl ConfigError {
     }
 }
 
+/// Writes `, did you mean x?`, or `, did you mean one of x, y?`, if there are suggestions.
+fn write_suggestions(
+    f: &mut fmt::Formatter<'_>,
+    suggestions: &[String],
+    quote: impl Fn(&mut fmt::Formatter<'_>, &str) -> fmt::Result,
+) -> fmt::Result {
+    match suggestions.len() {
+        0 => return Ok(()),
+        1 => write!(f, ", did you mean ")?,
+        _ => write!(f, ", did you mean one of ")?,
+    }
+
+    for (i, suggestion) in suggestions.iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        quote(f, suggestion)?;
+    }
+
+    write!(f, "?")
+}
+
 /// Alias for a `Result` with the error type set to `ConfigError`.
 pub(crate) type Result<T> = result::Result<T, ConfigError>;
 
@@ -194,12 +329,14 @@ impl fmt::Display for ConfigError {
 
             ConfigError::Foreign(ref cause) => write!(f, "{cause}"),
 
-            ConfigError::NotFound(ref key) => {
-                write!(f, "configuration property {key:?} not found")
+            ConfigError::NotFound(ref key, ref suggestions) => {
+                write!(f, "configuration property {key:?} not found")?;
+                write_suggestions(f, suggestions, |f, s| write!(f, "{s:?}"))
             }
 
             ConfigError::Type {
                 ref origin,
+                ref location,
                 ref unexpected,
                 expected,
                 ref key,
@@ -212,12 +349,69 @@ impl fmt::Display for ConfigError {
 
                 if let Some(ref origin) = *origin {
                     write!(f, " in {origin}")?;
+
+                    if let Some(location) = location {
+                        write!(f, ":{location}")?;
+                    }
                 }
 
                 Ok(())
             }
 
-            ConfigError::FileParse { ref cause, ref uri } => {
+            ConfigError::MissingField {
+                field,
+                ref key,
+                ref suggestions,
+            } => {
+                write!(f, "missing field `{field}`")?;
+
+                if let Some(ref key) = *key {
+                    write!(f, " for key `{key}`")?;
+                }
+
+                write_suggestions(f, suggestions, |f, s| write!(f, "`{s}`"))
+            }
+
+            ConfigError::UnknownField {
+                ref field,
+                expected,
+                ref key,
+                ref suggestions,
+            } => {
+                write!(f, "unknown field `{field}`")?;
+
+                if let Some(ref key) = *key {
+                    write!(f, " for key `{key}`")?;
+                }
+
+                if !suggestions.is_empty() {
+                    write_suggestions(f, suggestions, |f, s| write!(f, "`{s}`"))
+                } else if expected.is_empty() {
+                    write!(f, ", there are no fields")
+                } else {
+                    let expected: Vec<_> = expected.iter().map(|e| format!("`{e}`")).collect();
+                    write!(f, ", expected one of {}", expected.join(", "))
+                }
+            }
+
+            ConfigError::Multiple(ref errors) => {
+                let plural = if errors.len() == 1 { "" } else { "s" };
+                write!(
+                    f,
+                    "found {} error{plural} in the configuration:",
+                    errors.len()
+                )?;
+
+                for error in errors {
+                    write!(f, "\n  {error}")?;
+                }
+
+                Ok(())
+            }
+
+            ConfigError::FileParse {
+                ref cause, ref uri, ..
+            } => {
                 write!(f, "{cause}")?;
 
                 if let Some(ref uri) = *uri {
@@ -236,6 +430,23 @@ impl de::Error for ConfigError {
     fn custom<T: fmt::Display>(msg: T) -> Self {
         Self::Message(msg.to_string())
     }
+
+    fn missing_field(field: &'static str) -> Self {
+        Self::MissingField {
+            field,
+            key: None,
+            suggestions: Vec::new(),
+        }
+    }
+
+    fn unknown_field(field: &str, expected: &'static [&'static str]) -> Self {
+        Self::UnknownField {
+            field: field.to_owned(),
+            expected,
+            key: None,
+            suggestions: suggest::suggest(field, expected.iter().copied()),
+        }
+    }
 }
 
 impl ser::Error for ConfigError {
@@ -0,0 +1,240 @@
+use std::cmp::Ordering;
+use std::ffi::{OsStr, OsString};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::error::{ConfigError, Result};
+use crate::file::{File, FileFormat};
+use crate::map::Map;
+use crate::path::Expression;
+use crate::source::Source;
+use crate::value::{Value, ValueKind};
+
+/// A configuration source reading every file of a directory, such as `/etc/myapp/conf.d`.
+///
+/// Files with the extension of a registered [`FileFormat`] are read in the [order](DirectoryOrder)
+/// of their names, each one merged over the previous ones. Other files, and files and directories
+/// whose name starts with a dot, are ignored.
+///
+/// ```rust,no_run
+/// # use config::*;
+/// # fn main() -> Result<(), ConfigError> {
+/// let config = Config::builder()
+///     .add_source(File::with_name("/etc/myapp/config"))
+///     .add_source(
+///         Directory::new("/etc/myapp/conf.d")
+///             .order(DirectoryOrder::Numeric)
+///             .required(false),
+///     )
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct Directory {
+    path: PathBuf,
+    order: DirectoryOrder,
+    recursive: bool,
+    nested: bool,
+    required: bool,
+}
+
+/// The order in which a [`Directory`] reads its files, later files overriding earlier ones.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DirectoryOrder {
+    /// By name, so that `10-b.toml` comes before `9-a.toml`.
+    #[default]
+    Lexical,
+
+    /// By the number the names start with, so that `9-a.toml` comes before `10-b.toml`, then by
+    /// name. Files without a number come last.
+    Numeric,
+}
+
+impl Directory {
+    /// Reads the files directly in the directory at `path`, merged at the root.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            order: DirectoryOrder::default(),
+            recursive: false,
+            nested: false,
+            required: true,
+        }
+    }
+
+    /// Sets the order in which the files are read.
+    pub fn order(mut self, order: DirectoryOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Reads the files of the subdirectories as well, in place of the subdirectory in the order.
+    pub fn recursive(mut self, recursive: bool) -> Self {
+        self.recursive = recursive;
+        self
+    }
+
+    /// Sets the values of each file under the stem of its name rather than at the root, and
+    /// those of the subdirectories under their name, so that `conf.d/database.toml` sets
+    /// `database.host` for instance.
+    ///
+    /// With [`DirectoryOrder::Numeric`], the number and the `-`, `_` or `.` after it are left out
+    /// of the key: `10-database.toml` sets `database` as well.
+    pub fn nested(mut self, nested: bool) -> Self {
+        self.nested = nested;
+        self
+    }
+
+    /// Set required to false to ignore the directory if it does not exist when building the
+    /// config.
+    pub fn required(mut self, required: bool) -> Self {
+        self.required = required;
+        self
+    }
+
+    /// Merges the files of `directory` into `root`, under `prefix` if nested.
+    fn read(&self, directory: &Path, prefix: Option<&Expression>, root: &mut Value) -> Result<()> {
+        let mut entries = Vec::new();
+        for entry in fs::read_dir(directory).map_err(|e| ConfigError::Foreign(Box::new(e)))? {
+            let entry = entry.map_err(|e| ConfigError::Foreign(Box::new(e)))?;
+            let name = entry.file_name();
+            if name.to_string_lossy().starts_with('.') {
+                continue;
+            }
+            entries.push((name, entry.path()));
+        }
+        entries.sort_by(|(a, _), (b, _)| self.compare(a, b));
+
+        for (name, path) in entries {
+            let name = Path::new(&name);
+            if path.is_dir() {
+                if self.recursive {
+                    let prefix = self.key(prefix, name.as_os_str());
+                    self.read(&path, prefix.as_ref(), root)?;
+                }
+                continue;
+            }
+
+            let extension = name.extension().unwrap_or_default().to_string_lossy();
+            let Some(format) = FileFormat::from_extension(&extension) else {
+                continue;
+            };
+            let map = File::from(path.clone()).format(format).collect()?;
+
+            let stem = name.file_stem().unwrap_or_default();
+            match self.key(prefix, stem) {
+                Some(key) => key.set(root, map.into()),
+                None => {
+                    for (key, value) in map {
+                        Expression::Identifier(key).set(root, value);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The key of the file or directory named `name` under `prefix`, if nested.
+    fn key(&self, prefix: Option<&Expression>, name: &OsStr) -> Option<Expression> {
+        if !self.nested {
+            return None;
+        }
+
+        let name = name.to_string_lossy();
+        let name = match self.order {
+            DirectoryOrder::Numeric if number(&name).is_some() => name
+                .trim_start_matches(|c: char| c.is_ascii_digit())
+                .trim_start_matches(['-', '_', '.']),
+            _ => &name,
+        };
+
+        Some(match prefix {
+            Some(prefix) => Expression::Child(Box::new(prefix.clone()), name.to_owned()),
+            None => Expression::Identifier(name.to_owned()),
+        })
+    }
+
+    fn compare(&self, a: &OsString, b: &OsString) -> Ordering {
+        match self.order {
+            DirectoryOrder::Lexical => a.cmp(b),
+            DirectoryOrder::Numeric => {
+                let (a_text, b_text) = (a.to_string_lossy(), b.to_string_lossy());
+                match (number(&a_text), number(&b_text)) {
+                    (Some(x), Some(y)) => x.cmp(&y).then_with(|| a.cmp(b)),
+                    (Some(_), None) => Ordering::Less,
+                    (None, Some(_)) => Ordering::Greater,
+                    (None, None) => a.cmp(b),
+                }
+            }
+        }
+    }
+}
+
+/// The number `name` starts with, if any.
+fn number(name: &str) -> Option<u64> {
+    let end = name
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(name.len());
+
+    name[..end].parse().ok()
+}
+
+/// Adds the subdirectories of `directory` a recursive [`Directory`] reads to `paths`.
+fn subdirectories(directory: &Path, paths: &mut Vec<PathBuf>) {
+    for entry in fs::read_dir(directory).into_iter().flatten().flatten() {
+        let path = entry.path();
+        if path.is_dir() && !entry.file_name().to_string_lossy().starts_with('.') {
+            paths.push(path.clone());
+            subdirectories(&path, paths);
+        }
+    }
+}
+
+impl Source for Directory {
+    fn clone_into_box(&self) -> Box<dyn Source + Send + Sync> {
+        Box::new((*self).clone())
+    }
+
+    fn describe(&self) -> String {
+        format!("directory \"{}\"", self.path.to_string_lossy())
+    }
+
+    fn watch_paths(&self) -> Vec<PathBuf> {
+        // The directories rather than their files, to notice files being added or removed
+        let mut paths = vec![self.path.clone()];
+        if self.recursive {
+            subdirectories(&self.path, &mut paths);
+        }
+
+        paths
+    }
+
+    fn collect(&self) -> Result<Map<String, Value>> {
+        if !self.path.is_dir() {
+            if !self.required {
+                return Ok(Map::new());
+            }
+
+            return Err(ConfigError::Foreign(Box::new(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!(
+                    "configuration directory \"{}\" not found",
+                    self.path.to_string_lossy()
+                ),
+            ))));
+        }
+
+        let mut root = Value::new(None, Map::<String, Value>::new());
+        self.read(&self.path, None, &mut root)?;
+
+        match root.kind {
+            ValueKind::Table(map) => Ok(map),
+            _ => unreachable!(),
+        }
+    }
+}
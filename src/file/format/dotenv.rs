@@ -0,0 +1,326 @@
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::iter::Peekable;
+use std::str::Chars;
+
+use crate::map::Map;
+use crate::value::{Location, Value, ValueKind};
+
+pub(crate) fn parse(
+    uri: Option<&String>,
+    text: &str,
+) -> Result<Map<String, Value>, Box<dyn Error + Send + Sync>> {
+    let mut map = Map::new();
+    for variable in parse_variables(text)? {
+        let mut value = Value::new(uri, ValueKind::String(variable.value));
+        value.location = Some(Location::new(variable.line, variable.column));
+        map.insert(variable.name, value);
+    }
+
+    Ok(map)
+}
+
+/// A variable set by a `.env` file, at the position of its value.
+#[derive(Debug)]
+pub(crate) struct Variable {
+    pub(crate) name: String,
+    pub(crate) value: String,
+    pub(crate) line: usize,
+    pub(crate) column: usize,
+}
+
+/// Parses the variables of a `.env` file, in the order they are set.
+///
+/// Lines hold `NAME=value`, optionally preceded by `export`. Values are either unquoted, up to
+/// the end of the line or a `#` comment, single-quoted and read as is, or double-quoted with
+/// `\n`, `\r`, `\t`, `\"`, `\\` and `\$` escapes. Quoted values may span several lines. Unquoted
+/// and double-quoted values expand `${NAME}` to the value of a variable set earlier in the file,
+/// or else in the environment.
+pub(crate) fn parse_variables(text: &str) -> Result<Vec<Variable>, DotenvError> {
+    let mut parser = Parser {
+        chars: text.chars().peekable(),
+        line: 1,
+        column: 1,
+        variables: Vec::new(),
+    };
+    parser.parse()?;
+
+    Ok(parser.variables)
+}
+
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+    column: usize,
+    variables: Vec<Variable>,
+}
+
+impl Parser<'_> {
+    fn parse(&mut self) -> Result<(), DotenvError> {
+        loop {
+            self.skip_while(char::is_whitespace);
+            match self.peek() {
+                None => return Ok(()),
+                Some('#') => {
+                    self.skip_while(|c| c != '\n');
+                    continue;
+                }
+                Some(_) => {}
+            }
+
+            let mut name = self.name()?;
+            if name == "export" && matches!(self.peek(), Some(' ' | '\t')) {
+                self.skip_while(is_blank);
+                name = self.name()?;
+            }
+
+            self.skip_while(is_blank);
+            if self.peek() != Some('=') {
+                return Err(self.error(format!("expected `=` after `{name}`")));
+            }
+            self.bump();
+            self.skip_while(is_blank);
+
+            let (line, column) = (self.line, self.column);
+            let value = match self.peek() {
+                Some('\'') => self.single_quoted()?,
+                Some('"') => self.double_quoted()?,
+                _ => self.unquoted()?,
+            };
+
+            self.skip_while(is_blank);
+            match self.peek() {
+                None | Some('\n' | '\r') => {}
+                Some('#') => self.skip_while(|c| c != '\n'),
+                Some(_) => return Err(self.error("unexpected character after the value".into())),
+            }
+
+            self.variables.push(Variable {
+                name,
+                value,
+                line,
+                column,
+            });
+        }
+    }
+
+    fn name(&mut self) -> Result<String, DotenvError> {
+        let mut name = String::new();
+        while let Some(c) = self.peek() {
+            if !(c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-')) {
+                break;
+            }
+            name.push(c);
+            self.bump();
+        }
+
+        if name.is_empty() {
+            return Err(self.error("expected a variable name".into()));
+        }
+
+        Ok(name)
+    }
+
+    fn unquoted(&mut self) -> Result<String, DotenvError> {
+        let mut value = String::new();
+        while let Some(c) = self.peek() {
+            match c {
+                '\n' => break,
+                // A comment needs some space before it
+                '#' if value.is_empty() || value.ends_with(is_blank) => break,
+                '$' => {
+                    self.bump();
+                    self.reference(&mut value)?;
+                }
+                _ => {
+                    value.push(c);
+                    self.bump();
+                }
+            }
+        }
+
+        Ok(value.trim_end().to_owned())
+    }
+
+    fn single_quoted(&mut self) -> Result<String, DotenvError> {
+        let error = self.error("unterminated single-quoted value".into());
+        self.bump();
+
+        let mut value = String::new();
+        loop {
+            match self.bump() {
+                None => return Err(error),
+                Some('\'') => return Ok(value),
+                Some(c) => value.push(c),
+            }
+        }
+    }
+
+    fn double_quoted(&mut self) -> Result<String, DotenvError> {
+        let error = self.error("unterminated double-quoted value".into());
+        self.bump();
+
+        let mut value = String::new();
+        loop {
+            match self.bump() {
+                None => return Err(error),
+                Some('"') => return Ok(value),
+                Some('\\') => match self.bump() {
+                    None => return Err(error),
+                    Some('n') => value.push('\n'),
+                    Some('r') => value.push('\r'),
+                    Some('t') => value.push('\t'),
+                    Some(c @ ('"' | '\\' | '$')) => value.push(c),
+                    Some(c) => {
+                        value.push('\\');
+                        value.push(c);
+                    }
+                },
+                Some('$') => self.reference(&mut value)?,
+                Some(c) => value.push(c),
+            }
+        }
+    }
+
+    /// Expands a `${NAME}` reference into `value`, the `$` being read already.
+    fn reference(&mut self, value: &mut String) -> Result<(), DotenvError> {
+        if self.peek() != Some('{') {
+            value.push('$');
+            return Ok(());
+        }
+        let error = self.error("unterminated `${` reference".into());
+        self.bump();
+
+        let mut name = String::new();
+        loop {
+            match self.bump() {
+                None | Some('\n') => return Err(error),
+                Some('}') => break,
+                Some(c) => name.push(c),
+            }
+        }
+
+        match self.variables.iter().rev().find(|v| v.name == name) {
+            Some(variable) => value.push_str(&variable.value),
+            None => value.push_str(&env::var(&name).unwrap_or_default()),
+        }
+
+        Ok(())
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().copied()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+
+        Some(c)
+    }
+
+    fn skip_while(&mut self, predicate: impl Fn(char) -> bool) {
+        while self.peek().is_some_and(&predicate) {
+            self.bump();
+        }
+    }
+
+    fn error(&self, message: String) -> DotenvError {
+        DotenvError {
+            line: self.line,
+            column: self.column,
+            message,
+        }
+    }
+}
+
+fn is_blank(c: char) -> bool {
+    c == ' ' || c == '\t'
+}
+
+pub(crate) fn render(values: &Map<String, Value>) -> Result<String, Box<dyn Error + Send + Sync>> {
+    let mut text = String::new();
+    render_table("", values, &mut text)?;
+
+    Ok(text)
+}
+
+/// Writes a line per value of `table`, nested tables with dotted names.
+fn render_table(
+    prefix: &str,
+    table: &Map<String, Value>,
+    text: &mut String,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let mut keys: Vec<&String> = table.keys().collect();
+    keys.sort();
+
+    for key in keys {
+        let name = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{prefix}.{key}")
+        };
+
+        match table[key].kind {
+            ValueKind::Table(ref table) => render_table(&name, table, text)?,
+            ValueKind::Array(_) => return Err(Box::new(UnsupportedValueError)),
+            ValueKind::Nil => text.push_str(&format!("{name}=\n")),
+            ValueKind::String(ref s) => {
+                let mut escaped = String::new();
+                for c in s.chars() {
+                    match c {
+                        '\n' => escaped.push_str("\\n"),
+                        '\r' => escaped.push_str("\\r"),
+                        '\t' => escaped.push_str("\\t"),
+                        '"' | '\\' | '$' => {
+                            escaped.push('\\');
+                            escaped.push(c);
+                        }
+                        _ => escaped.push(c),
+                    }
+                }
+                text.push_str(&format!("{name}=\"{escaped}\"\n"));
+            }
+            ref kind => text.push_str(&format!("{name}={kind}\n")),
+        }
+    }
+
+    Ok(())
+}
+
+/// An error in the syntax of a `.env` file.
+#[derive(Debug)]
+pub(crate) struct DotenvError {
+    pub(crate) line: usize,
+    pub(crate) column: usize,
+    pub(crate) message: String,
+}
+
+impl fmt::Display for DotenvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at line {} column {}",
+            self.message, self.line, self.column
+        )
+    }
+}
+
+impl Error for DotenvError {}
+
+#[derive(Debug, Clone)]
+struct UnsupportedValueError;
+
+impl fmt::Display for UnsupportedValueError {
+    fn fmt(&self, format: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(format, "dotenv does not support arrays")
+    }
+}
+
+impl Error for UnsupportedValueError {}
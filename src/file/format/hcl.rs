@@ -0,0 +1,206 @@
+use std::error::Error;
+use std::mem;
+
+use hcl::eval::{Context, Evaluate};
+use hcl::{Block, Body, Expression, ObjectKey, Structure};
+
+use crate::map::Map;
+use crate::value::{Value, ValueKind};
+
+/// Parses the attributes and blocks of an HCL body.
+///
+/// Attributes set their key. Blocks set a table keyed by their type, then by each of their
+/// labels, so that `backend "s3" { bucket = "logs" }` sets `backend.s3.bucket`. Blocks sharing
+/// their type and labels, such as repeated `ingress { ... }` blocks, become an array of tables.
+///
+/// Expressions that do not need any variable or function, such as `2 * 60` or heredocs, are
+/// evaluated. Other ones, such as `var.region` or `"${local.name}-db"`, are kept as strings of
+/// their source.
+///
+/// Fails if an attribute and a block, or two attributes, set the same key.
+///
+/// The values have no [`Location`](crate::Location), as the parser does not keep their positions.
+pub(crate) fn parse(
+    uri: Option<&String>,
+    text: &str,
+) -> Result<Map<String, Value>, Box<dyn Error + Send + Sync>> {
+    let body = hcl::parse(text)?;
+
+    from_body(uri, body)
+}
+
+fn from_body(
+    uri: Option<&String>,
+    body: Body,
+) -> Result<Map<String, Value>, Box<dyn Error + Send + Sync>> {
+    let mut map = Map::new();
+    for structure in body {
+        match structure {
+            Structure::Attribute(attribute) => {
+                let key = attribute.key.into_inner();
+                if map.contains_key(&key) {
+                    return Err(
+                        format!("attribute `{key}` conflicts with the value at `{key}`").into(),
+                    );
+                }
+                let value = from_expression(uri, attribute.expr);
+                map.insert(key, value);
+            }
+            Structure::Block(block) => insert_block(uri, &mut map, block)?,
+        }
+    }
+
+    Ok(map)
+}
+
+/// Inserts the body of `block` under its type and labels, turning a table already there into an
+/// array of both.
+///
+/// Fails if a value other than a table is in the way, such as the array of repeated `ingress`
+/// blocks followed by a labeled `ingress "x"` block.
+fn insert_block(
+    uri: Option<&String>,
+    map: &mut Map<String, Value>,
+    block: Block,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let mut keys: Vec<String> = vec![block.identifier.into_inner()];
+    keys.extend(block.labels.into_iter().map(|label| label.into_inner()));
+    let path = keys.join(".");
+    let conflict = |key: &str| format!("block `{path}` conflicts with the value at `{key}`");
+    let last = keys.pop().unwrap_or_default();
+
+    let mut table = map;
+    for (depth, key) in keys.iter().enumerate() {
+        let value = table
+            .entry(key.clone())
+            .or_insert_with(|| Value::new(uri, Map::<String, Value>::new()));
+        table = match value.kind {
+            ValueKind::Table(ref mut table) => table,
+            _ => return Err(conflict(&keys[..=depth].join(".")).into()),
+        };
+    }
+
+    let value = Value::new(uri, from_body(uri, block.body)?);
+    match table.get_mut(&last) {
+        Some(existing) => match existing.kind {
+            ValueKind::Array(ref mut items)
+                if items
+                    .iter()
+                    .all(|item| matches!(item.kind, ValueKind::Table(_))) =>
+            {
+                items.push(value);
+            }
+            ValueKind::Table(_) => {
+                let first = mem::take(existing);
+                *existing = Value::new(uri, vec![first, value]);
+            }
+            _ => return Err(conflict(&path).into()),
+        },
+        None => {
+            table.insert(last, value);
+        }
+    }
+
+    Ok(())
+}
+
+fn from_expression(uri: Option<&String>, expr: Expression) -> Value {
+    if let Ok(value) = expr.evaluate(&Context::new()) {
+        return from_hcl_value(uri, value);
+    }
+
+    // Parts of arrays and objects may still be evaluated
+    let kind = match expr {
+        Expression::Array(items) => ValueKind::Array(
+            items
+                .into_iter()
+                .map(|item| from_expression(uri, item))
+                .collect(),
+        ),
+        Expression::Object(object) => ValueKind::Table(
+            object
+                .into_iter()
+                .map(|(key, value)| (object_key(key), from_expression(uri, value)))
+                .collect(),
+        ),
+        Expression::TemplateExpr(template) => ValueKind::String(template.to_string()),
+        expr => ValueKind::String(expr.to_string()),
+    };
+
+    Value::new(uri, kind)
+}
+
+fn object_key(key: ObjectKey) -> String {
+    match key {
+        ObjectKey::Identifier(ident) => ident.into_inner(),
+        ObjectKey::Expression(Expression::String(s)) => s,
+        key => key.to_string(),
+    }
+}
+
+fn from_hcl_value(uri: Option<&String>, value: hcl::Value) -> Value {
+    let kind = match value {
+        hcl::Value::Null => ValueKind::Nil,
+        hcl::Value::Bool(value) => ValueKind::Boolean(value),
+        hcl::Value::Number(number) => {
+            if let Some(value) = number.as_i64() {
+                ValueKind::I64(value)
+            } else if let Some(value) = number.as_u64() {
+                ValueKind::U64(value)
+            } else {
+                ValueKind::Float(number.as_f64().unwrap_or_default())
+            }
+        }
+        hcl::Value::String(value) => ValueKind::String(value),
+        hcl::Value::Array(items) => ValueKind::Array(
+            items
+                .into_iter()
+                .map(|item| from_hcl_value(uri, item))
+                .collect(),
+        ),
+        hcl::Value::Object(object) => ValueKind::Table(
+            object
+                .into_iter()
+                .map(|(key, value)| (key, from_hcl_value(uri, value)))
+                .collect(),
+        ),
+    };
+
+    Value::new(uri, kind)
+}
+
+pub(crate) fn render(values: &Map<String, Value>) -> Result<String, Box<dyn Error + Send + Sync>> {
+    Ok(hcl::format::to_string(&to_body(values)?)?)
+}
+
+/// Writes tables as blocks, arrays of tables as repeated blocks and other values as attributes.
+fn to_body(table: &Map<String, Value>) -> Result<Body, Box<dyn Error + Send + Sync>> {
+    let mut keys: Vec<&String> = table.keys().collect();
+    keys.sort();
+
+    let mut body = Body::builder();
+    for key in keys {
+        let ident = hcl::Identifier::new(key.as_str())?;
+        let value = &table[key];
+        match value.kind {
+            ValueKind::Table(ref table) => {
+                body = body.add_block((ident, to_body(table)?));
+            }
+            ValueKind::Array(ref items)
+                if !items.is_empty()
+                    && items
+                        .iter()
+                        .all(|item| matches!(item.kind, ValueKind::Table(_))) =>
+            {
+                for item in items {
+                    if let ValueKind::Table(ref table) = item.kind {
+                        body = body.add_block((ident.clone(), to_body(table)?));
+                    }
+                }
+            }
+            _ => body = body.add_attribute((ident, hcl::to_expression(value)?)),
+        }
+    }
+
+    Ok(body.build())
+}
@@ -1,4 +1,5 @@
 use std::error::Error;
+use std::fmt;
 
 use ini::Ini;
 
@@ -35,3 +36,49 @@ pub(crate) fn parse(
     }
     Ok(map)
 }
+
+pub(crate) fn render(values: &Map<String, Value>) -> Result<String, Box<dyn Error + Send + Sync>> {
+    let mut ini = Ini::new();
+    for (key, value) in values {
+        match value.kind {
+            ValueKind::Table(ref table) => {
+                let mut section = ini.with_section(Some(key.as_str()));
+                for (sec_key, sec_value) in table {
+                    section.set(sec_key.as_str(), to_ini_string(sec_value)?);
+                }
+            }
+            _ => {
+                ini.with_general_section()
+                    .set(key.as_str(), to_ini_string(value)?);
+            }
+        }
+    }
+
+    let mut text = Vec::new();
+    ini.write_to(&mut text)?;
+    Ok(String::from_utf8(text)?)
+}
+
+fn to_ini_string(value: &Value) -> Result<String, Box<dyn Error + Send + Sync>> {
+    match value.kind {
+        ValueKind::Nil => Ok(String::new()),
+        ValueKind::Table(_) | ValueKind::Array(_) => {
+            Err(Box::new(UnsupportedValueError(value.kind.clone())))
+        }
+        ref kind => Ok(kind.to_string()),
+    }
+}
+
+#[derive(Debug, Clone)]
+struct UnsupportedValueError(ValueKind);
+
+impl fmt::Display for UnsupportedValueError {
+    fn fmt(&self, format: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            ValueKind::Array(_) => write!(format, "INI does not support arrays"),
+            _ => write!(format, "INI does not support tables nested in sections"),
+        }
+    }
+}
+
+impl Error for UnsupportedValueError {}
@@ -1,5 +1,6 @@
 use std::error::Error;
 
+use crate::file::format::location;
 use crate::format;
 use crate::map::Map;
 use crate::value::{Value, ValueKind};
@@ -9,10 +10,15 @@ pub(crate) fn parse(
     text: &str,
 ) -> Result<Map<String, Value>, Box<dyn Error + Send + Sync>> {
     // Parse a JSON object value from the text
-    let value = from_json_value(uri, &serde_json::from_str(text)?);
+    let mut value = from_json_value(uri, &serde_json::from_str(text)?);
+    location::locate_json(text).attach(&mut value);
     format::extract_root_table(uri, value)
 }
 
+pub(crate) fn render(values: &Map<String, Value>) -> Result<String, Box<dyn Error + Send + Sync>> {
+    Ok(serde_json::to_string_pretty(values)?)
+}
+
 fn from_json_value(uri: Option<&String>, value: &serde_json::Value) -> Value {
     match *value {
         serde_json::Value::String(ref value) => Value::new(uri, ValueKind::String(value.clone())),
@@ -1,5 +1,6 @@
 use std::error::Error;
 
+use crate::file::format::location;
 use crate::format;
 use crate::map::Map;
 use crate::value::{Value, ValueKind};
@@ -20,10 +21,15 @@ pub(crate) fn parse(
     uri: Option<&String>,
     text: &str,
 ) -> Result<Map<String, Value>, Box<dyn Error + Send + Sync>> {
-    let value = from_json5_value(uri, json5_rs::from_str::<Val>(text)?);
+    let mut value = from_json5_value(uri, json5_rs::from_str::<Val>(text)?);
+    location::locate_json(text).attach(&mut value);
     format::extract_root_table(uri, value)
 }
 
+pub(crate) fn render(values: &Map<String, Value>) -> Result<String, Box<dyn Error + Send + Sync>> {
+    Ok(json5_rs::to_string(values)?)
+}
+
 fn from_json5_value(uri: Option<&String>, value: Val) -> Value {
     let vk = match value {
         Val::Null => ValueKind::Nil,
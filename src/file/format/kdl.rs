@@ -0,0 +1,264 @@
+use std::convert::{TryFrom, TryInto};
+use std::error::Error;
+use std::fmt;
+use std::ops::Range;
+
+use kdl::{KdlDocument, KdlEntry, KdlNode, KdlValue};
+
+use super::location::LineIndex;
+use crate::map::Map;
+use crate::value::{Location, Value, ValueKind};
+
+/// The key of the arguments of a node with properties or children.
+const ARGS_KEY: &str = "args";
+
+/// Parses the nodes of a KDL document, as described in
+/// [`FileFormat::Kdl`](crate::FileFormat::Kdl).
+///
+/// ```kdl
+/// server "main" port=8080 {
+///     host "localhost"
+///     alias "www"
+///     alias "api"
+/// }
+/// zones "a" "b"
+/// ```
+///
+/// sets `server.args` to `["main"]`, `server.port` to `8080`, `server.host` to `"localhost"`,
+/// `server.alias` to `["www", "api"]` and `zones` to `["a", "b"]`. Type annotations are ignored.
+///
+/// A property and a child with the same name, or an `args` property or child beside arguments,
+/// are an error.
+pub(crate) fn parse(
+    uri: Option<&String>,
+    text: &str,
+) -> Result<Map<String, Value>, Box<dyn Error + Send + Sync>> {
+    let index = LineIndex::new(text);
+    let document = KdlDocument::parse(text).map_err(|error| {
+        let diagnostic = error.diagnostics.first();
+        KdlError {
+            location: index
+                .locate(diagnostic.map_or(0..0, |d| range(d.span.offset(), d.span.len()))),
+            message: diagnostic
+                .and_then(|d| d.message.clone().or_else(|| d.label.clone()))
+                .unwrap_or_else(|| error.to_string()),
+        }
+    })?;
+
+    Ok(from_document(uri, &document, &index)?)
+}
+
+fn from_document(
+    uri: Option<&String>,
+    document: &KdlDocument,
+    index: &LineIndex<'_>,
+) -> Result<Map<String, Value>, KdlError> {
+    let mut nodes: Map<String, Vec<Value>> = Map::new();
+    for node in document.nodes() {
+        nodes
+            .entry(node.name().value().to_owned())
+            .or_default()
+            .push(from_node(uri, node, index)?);
+    }
+
+    Ok(nodes
+        .into_iter()
+        .map(|(name, mut values)| {
+            let value = if values.len() == 1 {
+                values.remove(0)
+            } else {
+                let location = values[0].location.clone();
+                let mut value = Value::new(uri, values);
+                value.location = location;
+                value
+            };
+            (name, value)
+        })
+        .collect())
+}
+
+fn from_node(
+    uri: Option<&String>,
+    node: &KdlNode,
+    index: &LineIndex<'_>,
+) -> Result<Value, KdlError> {
+    let span = node.span();
+    let location = index.locate(range(span.offset(), span.len()));
+    let error = |message: String| KdlError {
+        location: location.clone(),
+        message: format!("{message} in node `{}`", node.name().value()),
+    };
+
+    let mut args = Vec::new();
+    let mut table = Map::new();
+    for entry in node.entries() {
+        let value = from_entry(uri, entry, index);
+        match entry.name() {
+            // The rightmost property wins
+            Some(name) => {
+                table.insert(name.value().to_owned(), value);
+            }
+            None => args.push(value),
+        }
+    }
+    if let Some(children) = node.children() {
+        for (name, child) in from_document(uri, children, index)? {
+            if table.contains_key(&name) {
+                return Err(error(format!("child `{name}` has the name of a property")));
+            }
+            table.insert(name, child);
+        }
+    }
+
+    let kind = if table.is_empty() && node.children().is_none() {
+        match args.len() {
+            0 => ValueKind::Nil,
+            1 => return Ok(args.remove(0)),
+            _ => ValueKind::Array(args),
+        }
+    } else {
+        if !args.is_empty() {
+            if table.contains_key(ARGS_KEY) {
+                return Err(error(format!(
+                    "`{ARGS_KEY}` property or child is beside arguments"
+                )));
+            }
+            let mut args = Value::new(uri, args);
+            args.location = Some(location.clone());
+            table.insert(ARGS_KEY.to_owned(), args);
+        }
+        ValueKind::Table(table)
+    };
+
+    let mut value = Value::new(uri, kind);
+    value.location = Some(location);
+
+    Ok(value)
+}
+
+fn from_entry(uri: Option<&String>, entry: &KdlEntry, index: &LineIndex<'_>) -> Value {
+    let kind = match entry.value() {
+        KdlValue::String(value) => ValueKind::String(value.clone()),
+        KdlValue::Integer(value) => match i64::try_from(*value) {
+            Ok(value) => ValueKind::I64(value),
+            Err(_) => ValueKind::I128(*value),
+        },
+        KdlValue::Float(value) => ValueKind::Float(*value),
+        KdlValue::Bool(value) => ValueKind::Boolean(*value),
+        KdlValue::Null => ValueKind::Nil,
+    };
+
+    let mut value = Value::new(uri, kind);
+    let span = entry.span();
+    value.location = Some(index.locate(range(span.offset(), span.len())));
+
+    value
+}
+
+/// The range of `len` bytes from `offset`, as the spans of kdl hold them.
+fn range(offset: usize, len: usize) -> Range<usize> {
+    offset..offset + len
+}
+
+pub(crate) fn render(values: &Map<String, Value>) -> Result<String, Box<dyn Error + Send + Sync>> {
+    let mut document = to_document(values, false)?;
+    document.autoformat();
+
+    Ok(document.to_string())
+}
+
+/// Writes a node per value of `table`, and for arrays holding tables or arrays, a node per item.
+fn to_document(
+    table: &Map<String, Value>,
+    skip_args: bool,
+) -> Result<KdlDocument, Box<dyn Error + Send + Sync>> {
+    let mut keys: Vec<&String> = table.keys().collect();
+    keys.sort();
+
+    let mut document = KdlDocument::new();
+    for key in keys {
+        let value = &table[key];
+        if skip_args && key == ARGS_KEY && arguments(value).is_some() {
+            continue;
+        }
+
+        match value.kind {
+            ValueKind::Array(ref items) if arguments(value).is_none() => {
+                for item in items {
+                    document.nodes_mut().push(to_node(key, item)?);
+                }
+            }
+            _ => document.nodes_mut().push(to_node(key, value)?),
+        }
+    }
+
+    Ok(document)
+}
+
+fn to_node(name: &str, value: &Value) -> Result<KdlNode, Box<dyn Error + Send + Sync>> {
+    let mut node = KdlNode::new(name);
+    let (args, children) = match value.kind {
+        ValueKind::Table(ref table) => (table.get(ARGS_KEY), Some(table)),
+        _ => (Some(value), None),
+    };
+
+    for arg in args.and_then(arguments).unwrap_or_default() {
+        node.entries_mut().push(KdlEntry::new(to_kdl_value(arg)?));
+    }
+    if let Some(children) = children {
+        node.set_children(to_document(children, true)?);
+    }
+
+    Ok(node)
+}
+
+/// The values `value` writes as arguments, if it is neither a table nor an array holding a table
+/// or an array.
+fn arguments(value: &Value) -> Option<Vec<&Value>> {
+    match value.kind {
+        ValueKind::Table(_) => None,
+        ValueKind::Array(ref items) => items
+            .iter()
+            .map(|item| match item.kind {
+                ValueKind::Table(_) | ValueKind::Array(_) => None,
+                _ => Some(item),
+            })
+            .collect(),
+        _ => Some(vec![value]),
+    }
+}
+
+fn to_kdl_value(value: &Value) -> Result<KdlValue, Box<dyn Error + Send + Sync>> {
+    Ok(match value.kind {
+        ValueKind::Nil => KdlValue::Null,
+        ValueKind::Boolean(value) => KdlValue::Bool(value),
+        ValueKind::I64(value) => KdlValue::Integer(value.into()),
+        ValueKind::I128(value) => KdlValue::Integer(value),
+        ValueKind::U64(value) => KdlValue::Integer(value.into()),
+        ValueKind::U128(value) => KdlValue::Integer(value.try_into()?),
+        ValueKind::Float(value) => KdlValue::Float(value),
+        ValueKind::String(ref value) => KdlValue::String(value.clone()),
+        ValueKind::Table(_) | ValueKind::Array(_) => unreachable!(),
+    })
+}
+
+/// An error in the syntax of a KDL document.
+#[derive(Debug)]
+pub(crate) struct KdlError {
+    pub(crate) location: Location,
+    pub(crate) message: String,
+}
+
+impl fmt::Display for KdlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at line {} column {}",
+            self.message,
+            self.location.line(),
+            self.location.column()
+        )
+    }
+}
+
+impl Error for KdlError {}
@@ -0,0 +1,325 @@
+#[cfg(any(feature = "json", feature = "json5"))]
+use std::collections::HashMap;
+use std::ops::Range;
+
+use crate::value::Location;
+#[cfg(any(feature = "json", feature = "json5"))]
+use crate::value::{Value, ValueKind};
+
+/// Locations of the values of a parsed document, mirroring its structure.
+///
+/// Formats build it in a second pass over the text, once it is known to parse,
+/// and [`attach`](Self::attach) it to the values the parser produced.
+#[cfg(any(feature = "json", feature = "json5"))]
+#[derive(Debug, Default)]
+pub(crate) struct Located {
+    pub(crate) location: Option<Location>,
+    pub(crate) entries: HashMap<String, Located>,
+    pub(crate) items: Vec<Located>,
+}
+
+#[cfg(any(feature = "json", feature = "json5"))]
+impl Located {
+    pub(crate) fn attach(&self, value: &mut Value) {
+        if self.location.is_some() {
+            value.location = self.location.clone();
+        }
+
+        match value.kind {
+            ValueKind::Table(ref mut table) => {
+                for (key, value) in table {
+                    if let Some(located) = self.entries.get(key) {
+                        located.attach(value);
+                    }
+                }
+            }
+            ValueKind::Array(ref mut array) => {
+                for (located, value) in self.items.iter().zip(array) {
+                    located.attach(value);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Translates byte offsets of a text into lines and columns.
+pub(crate) struct LineIndex<'a> {
+    text: &'a str,
+    starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    pub(crate) fn new(text: &'a str) -> Self {
+        let starts = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+
+        Self { text, starts }
+    }
+
+    pub(crate) fn locate(&self, span: Range<usize>) -> Location {
+        let line = self.starts.partition_point(|&start| start <= span.start);
+        let start = self.starts[line - 1];
+        let column = self.text[start..span.start].chars().count() + 1;
+
+        Location::new(line, column).with_span(span)
+    }
+}
+
+/// Reads through a text one character at a time.
+#[cfg(any(feature = "json", feature = "json5"))]
+pub(crate) struct Cursor<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+#[cfg(any(feature = "json", feature = "json5"))]
+impl<'a> Cursor<'a> {
+    pub(crate) fn new(text: &'a str) -> Self {
+        Self { text, pos: 0 }
+    }
+
+    pub(crate) fn pos(&self) -> usize {
+        self.pos
+    }
+
+    pub(crate) fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    pub(crate) fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    pub(crate) fn rest(&self) -> &'a str {
+        &self.text[self.pos..]
+    }
+
+    pub(crate) fn starts_with(&self, s: &str) -> bool {
+        self.rest().starts_with(s)
+    }
+
+    pub(crate) fn eat(&mut self, s: &str) -> bool {
+        let found = self.starts_with(s);
+        if found {
+            self.pos += s.len();
+        }
+        found
+    }
+
+    pub(crate) fn eat_while(&mut self, mut predicate: impl FnMut(char) -> bool) -> &'a str {
+        let start = self.pos;
+        while self.peek().is_some_and(&mut predicate) {
+            self.bump();
+        }
+        &self.text[start..self.pos]
+    }
+
+    /// Skips past the next occurrence of `s`, or to the end of the text.
+    pub(crate) fn skip_past(&mut self, s: &str) {
+        match self.rest().find(s) {
+            Some(i) => self.pos += i + s.len(),
+            None => self.pos = self.text.len(),
+        }
+    }
+
+    /// Reads `n` hexadecimal digits as a code point.
+    pub(crate) fn hex(&mut self, n: usize) -> Option<u32> {
+        let digits = self.text.get(self.pos..self.pos + n)?;
+        let code = u32::from_str_radix(digits, 16).ok()?;
+        self.pos += n;
+        Some(code)
+    }
+}
+
+/// Locates the values of a JSON or JSON5 document.
+///
+/// The scanner reads the text the parser accepted, if it still fails to read through it the
+/// values located up to the failure keep their location. Values it reads under another key than
+/// the parser does, such as JSON5 keys holding escapes, are left without one.
+#[cfg(any(feature = "json", feature = "json5"))]
+pub(crate) fn locate_json(text: &str) -> Located {
+    let index = LineIndex::new(text);
+    let mut cursor = Cursor::new(text);
+    let mut root = Located::default();
+
+    // The locations are a best effort, failing to read the text is not an error
+    json_value(&mut cursor, &index, &mut root);
+    root
+}
+
+/// Locates the value at the cursor into `located`, which is only located itself once all of it
+/// was read.
+#[cfg(any(feature = "json", feature = "json5"))]
+fn json_value(cursor: &mut Cursor<'_>, index: &LineIndex<'_>, located: &mut Located) -> Option<()> {
+    json_trivia(cursor);
+    let start = cursor.pos();
+
+    match cursor.peek()? {
+        '{' => {
+            cursor.bump();
+            loop {
+                json_trivia(cursor);
+                match cursor.peek()? {
+                    '}' => {
+                        cursor.bump();
+                        break;
+                    }
+                    ',' => {
+                        cursor.bump();
+                        continue;
+                    }
+                    _ => {}
+                }
+
+                let key = match cursor.peek()? {
+                    '"' | '\'' => json_string(cursor)?,
+                    _ => cursor
+                        .eat_while(|c| c != ':' && c != '/' && !c.is_whitespace())
+                        .to_owned(),
+                };
+                json_trivia(cursor);
+                if !cursor.eat(":") {
+                    return None;
+                }
+                json_value(cursor, index, located.entries.entry(key).or_default())?;
+            }
+        }
+        '[' => {
+            cursor.bump();
+            loop {
+                json_trivia(cursor);
+                match cursor.peek()? {
+                    ']' => {
+                        cursor.bump();
+                        break;
+                    }
+                    ',' => {
+                        cursor.bump();
+                        continue;
+                    }
+                    _ => {}
+                }
+
+                located.items.push(Located::default());
+                json_value(cursor, index, located.items.last_mut()?)?;
+            }
+        }
+        '"' | '\'' => {
+            json_string(cursor)?;
+        }
+        _ => {
+            let token =
+                cursor.eat_while(|c| !matches!(c, ',' | ']' | '}' | '/') && !c.is_whitespace());
+            if token.is_empty() {
+                return None;
+            }
+        }
+    }
+
+    located.location = Some(index.locate(start..cursor.pos()));
+    Some(())
+}
+
+/// Skips whitespace and JSON5 comments.
+#[cfg(any(feature = "json", feature = "json5"))]
+fn json_trivia(cursor: &mut Cursor<'_>) {
+    loop {
+        cursor.eat_while(|c| c.is_whitespace() || c == '\u{feff}');
+        if cursor.eat("//") {
+            cursor.skip_past("\n");
+        } else if cursor.eat("/*") {
+            cursor.skip_past("*/");
+        } else {
+            break;
+        }
+    }
+}
+
+/// Reads a double or single quoted string, decoding its escapes.
+#[cfg(any(feature = "json", feature = "json5"))]
+fn json_string(cursor: &mut Cursor<'_>) -> Option<String> {
+    let quote = cursor.bump()?;
+    let mut s = String::new();
+
+    loop {
+        match cursor.bump()? {
+            c if c == quote => return Some(s),
+            '\\' => match cursor.bump()? {
+                'b' => s.push('\u{8}'),
+                'f' => s.push('\u{c}'),
+                'n' => s.push('\n'),
+                'r' => s.push('\r'),
+                't' => s.push('\t'),
+                'v' => s.push('\u{b}'),
+                '0' => s.push('\0'),
+                'x' => s.push(char::from_u32(cursor.hex(2)?)?),
+                'u' => {
+                    let mut code = cursor.hex(4)?;
+                    if (0xD800..0xDC00).contains(&code) && cursor.eat("\\u") {
+                        let low = cursor.hex(4)?;
+                        code =
+                            0x10000 + ((code - 0xD800) << 10) + (low.wrapping_sub(0xDC00) & 0x3FF);
+                    }
+                    s.push(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+                }
+                // Escaped line terminators continue the string on the next line
+                '\r' => {
+                    cursor.eat("\n");
+                }
+                '\n' | '\u{2028}' | '\u{2029}' => {}
+                c => s.push(c),
+            },
+            c => s.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(any(feature = "json", feature = "json5"))]
+mod test {
+    use super::*;
+
+    fn position(located: &Located) -> Option<(usize, usize)> {
+        located
+            .location
+            .as_ref()
+            .map(|location| (location.line(), location.column()))
+    }
+
+    #[test]
+    fn test_locate_json() {
+        let root = locate_json("{\"a\": [1, {\"b\": \"x\\\"y\"}],\n\"c\": null}");
+
+        assert_eq!(position(&root), Some((1, 1)));
+        assert_eq!(position(&root.entries["a"].items[1]), Some((1, 11)));
+        assert_eq!(
+            position(&root.entries["a"].items[1].entries["b"]),
+            Some((1, 17))
+        );
+        assert_eq!(position(&root.entries["c"]), Some((2, 6)));
+    }
+
+    #[test]
+    fn test_locate_json_failure() {
+        // A text cut short keeps the locations read before the end
+        let root = locate_json("{\"a\": 1, \"b\": [2, 3");
+
+        assert_eq!(position(&root), None);
+        assert_eq!(position(&root.entries["a"]), Some((1, 7)));
+        assert_eq!(position(&root.entries["b"]), None);
+        assert_eq!(position(&root.entries["b"].items[1]), Some((1, 19)));
+    }
+
+    #[test]
+    fn test_locate_json_fallback() {
+        // The key is read as written, rather than as `ab`
+        let root = locate_json("{a\\u0062: 1, /* c */ d/* e */: 2}");
+
+        assert!(!root.entries.contains_key("ab"));
+        assert_eq!(position(&root.entries["d"]), Some((1, 32)));
+    }
+}
@@ -3,7 +3,8 @@ use std::error::Error;
 use std::sync::OnceLock;
 
 use crate::map::Map;
-use crate::{file::FileStoredFormat, value::Value, Format};
+use crate::value::{Location, Value};
+use crate::{file::FileStoredFormat, Format, FormatWriter};
 
 #[cfg(feature = "toml")]
 mod toml;
@@ -23,6 +24,33 @@ mod ron;
 #[cfg(feature = "json5")]
 mod json5;
 
+#[cfg(feature = "dotenv")]
+pub(crate) mod dotenv;
+
+#[cfg(feature = "properties")]
+pub(crate) mod properties;
+
+#[cfg(feature = "xml")]
+pub(crate) mod xml;
+
+#[cfg(feature = "hcl")]
+mod hcl;
+
+#[cfg(feature = "kdl")]
+pub(crate) mod kdl;
+
+#[cfg(any(
+    feature = "toml",
+    feature = "json",
+    feature = "json5",
+    feature = "xml",
+    feature = "kdl"
+))]
+mod location;
+
+#[cfg(feature = "xml")]
+pub use self::xml::XmlFormat;
+
 /// File formats provided by the library.
 ///
 /// Although it is possible to define custom formats using [`Format`] trait it is recommended to use `FileFormat` if possible.
@@ -52,6 +80,37 @@ pub enum FileFormat {
     /// JSON5 (parsed with json5)
     #[cfg(feature = "json5")]
     Json5,
+
+    /// Dotenv, the `NAME=value` lines of `.env` files
+    #[cfg(feature = "dotenv")]
+    Dotenv,
+
+    /// Java properties, with dotted keys setting nested tables. A key holding a value and also
+    /// the parent of other keys, such as `a.b` along with `a.b.c`, is an error.
+    #[cfg(feature = "properties")]
+    Properties,
+
+    /// XML (parsed with `quick_xml`), mapped as described in [`XmlFormat`]
+    #[cfg(feature = "xml")]
+    Xml,
+
+    /// HCL (parsed with `hcl-rs`), with blocks nested under their type and labels. Its values
+    /// have no [`Location`].
+    #[cfg(feature = "hcl")]
+    Hcl,
+
+    /// KDL (parsed with kdl), each node setting the key of its name in the table of its parent:
+    ///
+    /// - A node with a single argument sets it, with several arguments an array of them and
+    ///   with none nil.
+    /// - A node with properties or children sets a table of them, its arguments going under the
+    ///   `args` key.
+    /// - Sibling nodes with the same name set an array.
+    ///
+    /// A property and a child with the same name, or an `args` property or child beside
+    /// arguments, are an error.
+    #[cfg(feature = "kdl")]
+    Kdl,
 }
 
 pub(crate) fn all_extensions() -> &'static HashMap<FileFormat, Vec<&'static str>> {
@@ -79,6 +138,21 @@ pub(crate) fn all_extensions() -> &'static HashMap<FileFormat, Vec<&'static str>
         #[cfg(feature = "json5")]
         formats.insert(FileFormat::Json5, vec!["json5"]);
 
+        #[cfg(feature = "dotenv")]
+        formats.insert(FileFormat::Dotenv, vec!["env"]);
+
+        #[cfg(feature = "properties")]
+        formats.insert(FileFormat::Properties, vec!["properties"]);
+
+        #[cfg(feature = "xml")]
+        formats.insert(FileFormat::Xml, vec!["xml"]);
+
+        #[cfg(feature = "hcl")]
+        formats.insert(FileFormat::Hcl, vec!["hcl"]);
+
+        #[cfg(feature = "kdl")]
+        formats.insert(FileFormat::Kdl, vec!["kdl"]);
+
         formats
     })
 }
@@ -91,6 +165,14 @@ impl FileFormat {
         all_extensions().get(self).unwrap()
     }
 
+    /// Finds the format registered for the given file extension.
+    pub(crate) fn from_extension(extension: &str) -> Option<Self> {
+        all_extensions()
+            .iter()
+            .find(|(_, extensions)| extensions.contains(&extension))
+            .map(|(format, _)| *format)
+    }
+
     pub(crate) fn parse(
         &self,
         uri: Option<&String>,
@@ -115,6 +197,92 @@ impl FileFormat {
             #[cfg(feature = "json5")]
             FileFormat::Json5 => json5::parse(uri, text),
 
+            #[cfg(feature = "dotenv")]
+            FileFormat::Dotenv => dotenv::parse(uri, text),
+
+            #[cfg(feature = "properties")]
+            FileFormat::Properties => properties::parse(uri, text),
+
+            #[cfg(feature = "xml")]
+            FileFormat::Xml => xml::parse(uri, text, &XmlFormat::default()),
+
+            #[cfg(feature = "hcl")]
+            FileFormat::Hcl => hcl::parse(uri, text),
+
+            #[cfg(feature = "kdl")]
+            FileFormat::Kdl => kdl::parse(uri, text),
+
+            #[cfg(all(
+                not(feature = "toml"),
+                not(feature = "json"),
+                not(feature = "yaml"),
+                not(feature = "ini"),
+                not(feature = "ron"),
+                not(feature = "json5"),
+                not(feature = "dotenv"),
+                not(feature = "properties"),
+                not(feature = "xml"),
+                not(feature = "hcl"),
+                not(feature = "kdl"),
+            ))]
+            _ => unreachable!("No features are enabled, this library won't work without features"),
+        }
+    }
+
+    pub(crate) fn render(
+        &self,
+        #[cfg_attr(
+            all(
+                not(feature = "toml"),
+                not(feature = "json"),
+                not(feature = "yaml"),
+                not(feature = "ini"),
+                not(feature = "ron"),
+                not(feature = "json5"),
+                not(feature = "dotenv"),
+                not(feature = "properties"),
+                not(feature = "xml"),
+                not(feature = "hcl"),
+                not(feature = "kdl"),
+            ),
+            allow(unused_variables)
+        )]
+        values: &Map<String, Value>,
+    ) -> Result<String, Box<dyn Error + Send + Sync>> {
+        match self {
+            #[cfg(feature = "toml")]
+            FileFormat::Toml => toml::render(values),
+
+            #[cfg(feature = "json")]
+            FileFormat::Json => json::render(values),
+
+            #[cfg(feature = "yaml")]
+            FileFormat::Yaml => yaml::render(values),
+
+            #[cfg(feature = "ini")]
+            FileFormat::Ini => ini::render(values),
+
+            #[cfg(feature = "ron")]
+            FileFormat::Ron => ron::render(values),
+
+            #[cfg(feature = "json5")]
+            FileFormat::Json5 => json5::render(values),
+
+            #[cfg(feature = "dotenv")]
+            FileFormat::Dotenv => dotenv::render(values),
+
+            #[cfg(feature = "properties")]
+            FileFormat::Properties => properties::render(values),
+
+            #[cfg(feature = "xml")]
+            FileFormat::Xml => xml::render(values, &XmlFormat::default()),
+
+            #[cfg(feature = "hcl")]
+            FileFormat::Hcl => hcl::render(values),
+
+            #[cfg(feature = "kdl")]
+            FileFormat::Kdl => kdl::render(values),
+
             #[cfg(all(
                 not(feature = "toml"),
                 not(feature = "json"),
@@ -122,6 +290,11 @@ impl FileFormat {
                 not(feature = "ini"),
                 not(feature = "ron"),
                 not(feature = "json5"),
+                not(feature = "dotenv"),
+                not(feature = "properties"),
+                not(feature = "xml"),
+                not(feature = "hcl"),
+                not(feature = "kdl"),
             ))]
             _ => unreachable!("No features are enabled, this library won't work without features"),
         }
@@ -138,8 +311,126 @@ impl Format for FileFormat {
     }
 }
 
+impl FormatWriter for FileFormat {
+    fn render(&self, values: &Map<String, Value>) -> Result<String, Box<dyn Error + Send + Sync>> {
+        self.render(values)
+    }
+}
+
 impl FileStoredFormat for FileFormat {
     fn file_extensions(&self) -> &'static [&'static str] {
         self.extensions()
     }
 }
+
+/// Finds where and why the parser of a file format failed on `text`.
+pub(crate) fn locate_error(
+    #[cfg_attr(
+        all(
+            not(feature = "toml"),
+            not(feature = "json"),
+            not(feature = "yaml"),
+            not(feature = "ini"),
+            not(feature = "ron"),
+            not(feature = "json5"),
+            not(feature = "dotenv"),
+            not(feature = "properties"),
+            not(feature = "xml"),
+            not(feature = "hcl"),
+            not(feature = "kdl"),
+        ),
+        allow(unused_variables)
+    )]
+    cause: &(dyn Error + Send + Sync + 'static),
+    #[cfg_attr(not(feature = "toml"), allow(unused_variables))] text: &str,
+) -> Option<(Location, String)> {
+    #[cfg(feature = "toml")]
+    if let Some(error) = cause.downcast_ref::<toml_edit::TomlError>() {
+        let start = error.span()?.start.min(text.len());
+        let line_start = text[..start].rfind('\n').map_or(0, |i| i + 1);
+        let location = Location::new(
+            text[..start].matches('\n').count() + 1,
+            text[line_start..start].chars().count() + 1,
+        );
+
+        return Some((location, error.message().to_owned()));
+    }
+
+    #[cfg(feature = "json")]
+    if let Some(error) = cause.downcast_ref::<serde_json::Error>() {
+        let message = error.to_string();
+        let suffix = format!(" at line {} column {}", error.line(), error.column());
+        let message = message.strip_suffix(&suffix).unwrap_or(&message).to_owned();
+
+        return Some((Location::new(error.line(), error.column()), message));
+    }
+
+    #[cfg(feature = "yaml")]
+    if let Some(error) = cause.downcast_ref::<yaml_rust2::ScanError>() {
+        let location = Location::new(error.marker().line(), error.marker().col() + 1);
+
+        return Some((location, error.info().to_owned()));
+    }
+
+    #[cfg(feature = "ini")]
+    if let Some(error) = cause.downcast_ref::<::ini::ParseError>() {
+        return Some((Location::new(error.line, error.col), error.msg.to_string()));
+    }
+
+    #[cfg(feature = "ron")]
+    if let Some(error) = cause.downcast_ref::<::ron::error::SpannedError>() {
+        let location = Location::new(error.position.line, error.position.col);
+
+        return Some((location, error.code.to_string()));
+    }
+
+    #[cfg(feature = "json5")]
+    if let Some(json5_rs::Error::Message {
+        msg,
+        location: Some(location),
+    }) = cause.downcast_ref::<json5_rs::Error>()
+    {
+        // The message is formatted with a snippet of its own, keep only its last note
+        let message = msg.lines().last().unwrap_or_default().trim_start();
+
+        return Some((
+            Location::new(location.line, location.column),
+            message.trim_start_matches("= ").to_owned(),
+        ));
+    }
+
+    #[cfg(feature = "dotenv")]
+    if let Some(error) = cause.downcast_ref::<dotenv::DotenvError>() {
+        return Some((
+            Location::new(error.line, error.column),
+            error.message.clone(),
+        ));
+    }
+
+    #[cfg(feature = "properties")]
+    if let Some(error) = cause.downcast_ref::<properties::PropertiesError>() {
+        return Some((
+            Location::new(error.line, error.column),
+            error.message.clone(),
+        ));
+    }
+
+    #[cfg(feature = "xml")]
+    if let Some(error) = cause.downcast_ref::<xml::XmlError>() {
+        return Some((error.location.clone(), error.message.clone()));
+    }
+
+    #[cfg(feature = "hcl")]
+    if let Some(::hcl::Error::Parse(error)) = cause.downcast_ref::<::hcl::Error>() {
+        let location = Location::new(error.location().line(), error.location().column());
+
+        return Some((location, error.message().to_owned()));
+    }
+
+    #[cfg(feature = "kdl")]
+    if let Some(error) = cause.downcast_ref::<kdl::KdlError>() {
+        return Some((error.location.clone(), error.message.clone()));
+    }
+
+    None
+}
@@ -0,0 +1,307 @@
+use std::error::Error;
+use std::fmt;
+use std::iter::Peekable;
+use std::str::{Chars, FromStr};
+
+use crate::map::Map;
+use crate::path::Expression;
+use crate::value::{Location, Value, ValueKind};
+
+/// Parses the `key=value` lines of a Java `.properties` file.
+///
+/// Keys and values are separated by `=`, `:` or whitespace, lines starting with `#` or `!` are
+/// comments and a backslash at the end of a line continues it on the next one. Dotted keys,
+/// such as `server.port`, set nested tables. Every value is a string.
+///
+/// Fails if a key sets a value where another key already set one it would drop, such as
+/// `logging.level` along with `logging.level.org.foo`, in either order. Repeating a key replaces
+/// its value.
+pub(crate) fn parse(
+    uri: Option<&String>,
+    text: &str,
+) -> Result<Map<String, Value>, Box<dyn Error + Send + Sync>> {
+    let mut parser = Parser {
+        chars: text.chars().peekable(),
+        line: 1,
+        column: 1,
+    };
+
+    let mut root = Value::new(None, Map::<String, Value>::new());
+    while let Some((key, key_location, value, location)) = parser.next_property()? {
+        let mut value = Value::new(uri, ValueKind::String(value));
+        value.location = Some(location);
+
+        // Keys the path syntax rejects, for instance holding spaces, still nest at their dots
+        let expr = Expression::from_str(&key).unwrap_or_else(|_| {
+            let mut segments = key.split('.').map(str::to_owned);
+            let first = Expression::Identifier(segments.next().unwrap_or_default());
+            segments.fold(first, |parent, segment| {
+                Expression::Child(Box::new(parent), segment)
+            })
+        });
+        if let Some(path) = conflict(&expr, &root) {
+            let message = if path == expr {
+                format!("key `{key}` conflicts with the keys under it")
+            } else {
+                format!("key `{key}` conflicts with the value at `{path}`")
+            };
+            return Err(PropertiesError {
+                line: key_location.line(),
+                column: key_location.column(),
+                message,
+            }
+            .into());
+        }
+        expr.set(&mut root, value);
+    }
+
+    match root.kind {
+        ValueKind::Table(map) => Ok(map),
+        _ => unreachable!(),
+    }
+}
+
+/// The path of the value in `root` that setting `expr` would drop, either a value in the way of
+/// its parents, or the tables and arrays already under it.
+fn conflict(expr: &Expression, root: &Value) -> Option<Expression> {
+    if let Some(ValueKind::Table(_) | ValueKind::Array(_)) = expr.clone().get(root).map(|v| &v.kind)
+    {
+        return Some(expr.clone());
+    }
+
+    let mut child = expr;
+    loop {
+        let (parent, table) = match *child {
+            Expression::Identifier(_) => return None,
+            Expression::Child(ref parent, _) => (parent.as_ref(), true),
+            Expression::Subscript(ref parent, _) => (parent.as_ref(), false),
+        };
+        match parent.clone().get(root).map(|v| &v.kind) {
+            None | Some(ValueKind::Table(_)) if table => {}
+            None | Some(ValueKind::Array(_)) if !table => {}
+            _ => return Some(parent.clone()),
+        }
+        child = parent;
+    }
+}
+
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+    column: usize,
+}
+
+impl Parser<'_> {
+    fn next_property(
+        &mut self,
+    ) -> Result<Option<(String, Location, String, Location)>, PropertiesError> {
+        loop {
+            self.skip_while(|c| matches!(c, ' ' | '\t' | '\x0c' | '\r' | '\n'));
+            match self.peek() {
+                None => return Ok(None),
+                Some('#' | '!') => self.skip_while(|c| c != '\n'),
+                Some(_) => break,
+            }
+        }
+
+        let key_location = Location::new(self.line, self.column);
+        let key = self.text(true)?;
+        self.skip_while(is_blank);
+        if matches!(self.peek(), Some('=' | ':')) {
+            self.bump();
+            self.skip_while(is_blank);
+        }
+
+        let location = Location::new(self.line, self.column);
+        let value = self.text(false)?;
+
+        Ok(Some((key, key_location, value, location)))
+    }
+
+    /// Reads up to the end of the logical line, or if `key`, up to a separator.
+    fn text(&mut self, key: bool) -> Result<String, PropertiesError> {
+        let mut text = String::new();
+        while let Some(c) = self.peek() {
+            match c {
+                '\n' | '\r' => break,
+                '=' | ':' | ' ' | '\t' | '\x0c' if key => break,
+                '\\' => {
+                    self.bump();
+                    match self.bump() {
+                        None => break,
+                        Some('\r' | '\n') => {
+                            if self.peek() == Some('\n') {
+                                self.bump();
+                            }
+                            self.skip_while(is_blank);
+                        }
+                        Some('t') => text.push('\t'),
+                        Some('n') => text.push('\n'),
+                        Some('r') => text.push('\r'),
+                        Some('f') => text.push('\x0c'),
+                        Some('u') => text.push(self.unicode()?),
+                        Some(c) => text.push(c),
+                    }
+                }
+                _ => {
+                    text.push(c);
+                    self.bump();
+                }
+            }
+        }
+
+        Ok(text)
+    }
+
+    /// Reads the code point of a `\uXXXX` escape, followed by a second one for surrogate pairs.
+    fn unicode(&mut self) -> Result<char, PropertiesError> {
+        let high = self.hex()?;
+        if !(0xD800..0xDC00).contains(&high) {
+            return char::from_u32(high).ok_or_else(|| self.error("invalid `\\u` escape"));
+        }
+
+        if self.bump() != Some('\\') || self.bump() != Some('u') {
+            return Err(self.error("unpaired surrogate in `\\u` escape"));
+        }
+        let low = self.hex()?;
+        if !(0xDC00..0xE000).contains(&low) {
+            return Err(self.error("unpaired surrogate in `\\u` escape"));
+        }
+
+        char::from_u32(0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00))
+            .ok_or_else(|| self.error("invalid `\\u` escape"))
+    }
+
+    fn hex(&mut self) -> Result<u32, PropertiesError> {
+        let mut code = 0;
+        for _ in 0..4 {
+            let digit = self
+                .peek()
+                .and_then(|c| c.to_digit(16))
+                .ok_or_else(|| self.error("expected 4 hexadecimal digits in `\\u` escape"))?;
+            self.bump();
+            code = code * 16 + digit;
+        }
+
+        Ok(code)
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().copied()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+
+        Some(c)
+    }
+
+    fn skip_while(&mut self, predicate: impl Fn(char) -> bool) {
+        while self.peek().is_some_and(&predicate) {
+            self.bump();
+        }
+    }
+
+    fn error(&self, message: &str) -> PropertiesError {
+        PropertiesError {
+            line: self.line,
+            column: self.column,
+            message: message.to_owned(),
+        }
+    }
+}
+
+fn is_blank(c: char) -> bool {
+    matches!(c, ' ' | '\t' | '\x0c')
+}
+
+pub(crate) fn render(values: &Map<String, Value>) -> Result<String, Box<dyn Error + Send + Sync>> {
+    let mut text = String::new();
+    let mut keys: Vec<&String> = values.keys().collect();
+    keys.sort();
+    for key in keys {
+        render_value(&escape(key, true), &values[key], &mut text);
+    }
+
+    Ok(text)
+}
+
+/// Writes a line per value under `value`, nested tables with dotted keys and arrays with
+/// subscripts.
+fn render_value(key: &str, value: &Value, text: &mut String) {
+    match value.kind {
+        ValueKind::Table(ref table) => {
+            let mut keys: Vec<&String> = table.keys().collect();
+            keys.sort();
+            for child in keys {
+                render_value(
+                    &format!("{key}.{}", escape(child, true)),
+                    &table[child],
+                    text,
+                );
+            }
+        }
+        ValueKind::Array(ref array) => {
+            for (index, item) in array.iter().enumerate() {
+                render_value(&format!("{key}[{index}]"), item, text);
+            }
+        }
+        ValueKind::Nil => text.push_str(&format!("{key}=\n")),
+        ref kind => text.push_str(&format!("{key}={}\n", escape(&kind.to_string(), false))),
+    }
+}
+
+/// Escapes the characters a key or value cannot hold as is, and those outside of ASCII.
+fn escape(s: &str, key: bool) -> String {
+    let mut escaped = String::new();
+    for (i, c) in s.chars().enumerate() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\x0c' => escaped.push_str("\\f"),
+            '=' | ':' | '#' | '!' if key => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            // Leading spaces of values would be skipped
+            ' ' if key || i == 0 => escaped.push_str("\\ "),
+            ' '..='~' => escaped.push(c),
+            _ => {
+                let mut units = [0; 2];
+                for unit in c.encode_utf16(&mut units) {
+                    escaped.push_str(&format!("\\u{unit:04X}"));
+                }
+            }
+        }
+    }
+
+    escaped
+}
+
+/// An error in the syntax of a `.properties` file.
+#[derive(Debug)]
+pub(crate) struct PropertiesError {
+    pub(crate) line: usize,
+    pub(crate) column: usize,
+    pub(crate) message: String,
+}
+
+impl fmt::Display for PropertiesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at line {} column {}",
+            self.message, self.line, self.column
+        )
+    }
+}
+
+impl Error for PropertiesError {}
@@ -12,6 +12,13 @@ pub(crate) fn parse(
     format::extract_root_table(uri, value)
 }
 
+pub(crate) fn render(values: &Map<String, Value>) -> Result<String, Box<dyn Error + Send + Sync>> {
+    Ok(ron::ser::to_string_pretty(
+        values,
+        ron::ser::PrettyConfig::default(),
+    )?)
+}
+
 fn from_ron_value(
     uri: Option<&String>,
     value: ron::Value,
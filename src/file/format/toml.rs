@@ -1,45 +1,97 @@
 use std::error::Error;
 
+use std::ops::Range;
+
+use toml_edit::{ImDocument, Item, Table};
+
+use crate::file::format::location::LineIndex;
 use crate::format;
 use crate::map::Map;
-use crate::value::Value;
+use crate::value::{Location, Value, ValueKind};
 
 pub(crate) fn parse(
     uri: Option<&String>,
     text: &str,
 ) -> Result<Map<String, Value>, Box<dyn Error + Send + Sync>> {
-    // Parse a TOML value from the provided text
-    let value = from_toml_value(uri, &toml::from_str(text)?);
+    // Parse a TOML document from the provided text, keeping the spans of its values
+    let document = ImDocument::parse(text)?;
+    let index = LineIndex::new(text);
+    let value = from_toml_item(uri, document.as_item(), &index);
     format::extract_root_table(uri, value)
 }
 
-fn from_toml_value(uri: Option<&String>, value: &toml::Value) -> Value {
-    match *value {
-        toml::Value::String(ref value) => Value::new(uri, value.to_string()),
-        toml::Value::Float(value) => Value::new(uri, value),
-        toml::Value::Integer(value) => Value::new(uri, value),
-        toml::Value::Boolean(value) => Value::new(uri, value),
+pub(crate) fn render(values: &Map<String, Value>) -> Result<String, Box<dyn Error + Send + Sync>> {
+    Ok(toml::to_string_pretty(values)?)
+}
+
+fn from_toml_item(uri: Option<&String>, item: &Item, index: &LineIndex<'_>) -> Value {
+    let mut value = match *item {
+        Item::None => Value::new(uri, ValueKind::Nil),
+        Item::Value(ref value) => return from_toml_value(uri, value, index),
+        Item::Table(ref table) => from_toml_table(uri, table, index),
+        Item::ArrayOfTables(ref tables) => {
+            let mut l = Vec::new();
+
+            for table in tables {
+                l.push(from_toml_table(uri, table, index));
+            }
+
+            Value::new(uri, l)
+        }
+    };
+
+    value.location = locate(item.span(), index);
+    value
+}
+
+fn from_toml_table(uri: Option<&String>, table: &Table, index: &LineIndex<'_>) -> Value {
+    let mut m = Map::new();
+
+    for (key, item) in table {
+        m.insert(key.to_owned(), from_toml_item(uri, item, index));
+    }
+
+    let mut value = Value::new(uri, m);
+    value.location = locate(table.span(), index);
+    value
+}
 
-        toml::Value::Table(ref table) => {
+fn from_toml_value(uri: Option<&String>, value: &toml_edit::Value, index: &LineIndex<'_>) -> Value {
+    let mut v = match *value {
+        toml_edit::Value::String(ref value) => Value::new(uri, value.value().clone()),
+        toml_edit::Value::Float(ref value) => Value::new(uri, *value.value()),
+        toml_edit::Value::Integer(ref value) => Value::new(uri, *value.value()),
+        toml_edit::Value::Boolean(ref value) => Value::new(uri, *value.value()),
+
+        toml_edit::Value::InlineTable(ref table) => {
             let mut m = Map::new();
 
             for (key, value) in table {
-                m.insert(key.clone(), from_toml_value(uri, value));
+                m.insert(key.to_owned(), from_toml_value(uri, value, index));
             }
 
             Value::new(uri, m)
         }
 
-        toml::Value::Array(ref array) => {
+        toml_edit::Value::Array(ref array) => {
             let mut l = Vec::new();
 
             for value in array {
-                l.push(from_toml_value(uri, value));
+                l.push(from_toml_value(uri, value, index));
             }
 
             Value::new(uri, l)
         }
 
-        toml::Value::Datetime(ref datetime) => Value::new(uri, datetime.to_string()),
-    }
+        toml_edit::Value::Datetime(ref datetime) => Value::new(uri, datetime.value().to_string()),
+    };
+
+    v.location = locate(value.span(), index);
+    v
+}
+
+/// Locates a span reported by the parser. Values it did not read from the text, such as the
+/// tables implied by dotted keys, have none.
+fn locate(span: Option<Range<usize>>, index: &LineIndex<'_>) -> Option<Location> {
+    span.map(|span| index.locate(span))
 }
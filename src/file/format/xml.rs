@@ -0,0 +1,457 @@
+use std::error::Error;
+use std::fmt;
+use std::mem;
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+use super::location::LineIndex;
+use crate::file::FileStoredFormat;
+use crate::format::{Format, FormatWriter};
+use crate::map::Map;
+use crate::value::{Location, Value, ValueKind};
+
+/// The XML file format, with a configurable mapping of attributes and text.
+///
+/// The root element holds the top-level table, whatever its name. Below it:
+///
+/// - An element with attributes or children becomes a table, keyed by its name.
+/// - Attributes become keys of that table, prefixed with the
+///   [`attribute_prefix`](Self::attribute_prefix), none by default.
+/// - Text beside attributes or children goes under the [`text_key`](Self::text_key), `text` by
+///   default. An element holding nothing but text becomes a string.
+/// - An attribute and a child element with the same key, or text beside an attribute or a child
+///   element at the text key, are an error. Set a prefix to tell them apart.
+/// - Sibling elements with the same name become an array. A single element stays a table or a
+///   string, so that `<server/>` alone is not an array of one server.
+///
+/// Every value is a string, converted when it is read as another type. Comments and processing
+/// instructions are ignored. The document must have a single root element, holding a table
+/// rather than text.
+///
+/// ```xml
+/// <config>
+///   <server port="8080">
+///     <host>localhost</host>
+///     <alias>www</alias>
+///     <alias>api</alias>
+///   </server>
+///   <motd lang="en">Welcome</motd>
+/// </config>
+/// ```
+///
+/// sets `server.port` to `"8080"`, `server.host` to `"localhost"`, `server.alias` to
+/// `["www", "api"]`, `motd.lang` to `"en"` and `motd.text` to `"Welcome"`.
+///
+/// [`FileFormat::Xml`](crate::FileFormat::Xml) uses the default mapping, use this type to change
+/// it:
+///
+/// ```rust
+/// # use config::*;
+/// # fn main() -> Result<(), ConfigError> {
+/// let config = Config::builder()
+///     .add_source(File::from_str(
+///         r#"<config><motd lang="en">Welcome</motd></config>"#,
+///         XmlFormat::default().attribute_prefix("@").text_key("$value"),
+///     ))
+///     .build()?;
+///
+/// let motd = config.get_table("motd")?;
+/// assert_eq!(motd["@lang"].clone().into_string()?, "en");
+/// assert_eq!(motd["$value"].clone().into_string()?, "Welcome");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct XmlFormat {
+    attribute_prefix: String,
+    text_key: String,
+}
+
+impl Default for XmlFormat {
+    fn default() -> Self {
+        Self {
+            attribute_prefix: String::new(),
+            text_key: "text".into(),
+        }
+    }
+}
+
+impl XmlFormat {
+    /// Prefixes the keys of attributes, to tell them from child elements.
+    pub fn attribute_prefix(mut self, prefix: &str) -> Self {
+        self.attribute_prefix = prefix.into();
+        self
+    }
+
+    /// Sets the key of the text of elements with attributes or children.
+    pub fn text_key(mut self, key: &str) -> Self {
+        self.text_key = key.into();
+        self
+    }
+}
+
+impl Format for XmlFormat {
+    fn parse(
+        &self,
+        uri: Option<&String>,
+        text: &str,
+    ) -> Result<Map<String, Value>, Box<dyn Error + Send + Sync>> {
+        parse(uri, text, self)
+    }
+}
+
+impl FormatWriter for XmlFormat {
+    fn render(&self, values: &Map<String, Value>) -> Result<String, Box<dyn Error + Send + Sync>> {
+        render(values, self)
+    }
+}
+
+impl FileStoredFormat for XmlFormat {
+    fn file_extensions(&self) -> &'static [&'static str] {
+        &["xml"]
+    }
+}
+
+/// An element being read, with what it holds so far.
+struct Element {
+    name: String,
+    attributes: Map<String, Value>,
+    table: Map<String, Value>,
+    text: String,
+    location: Location,
+}
+
+pub(crate) fn parse(
+    uri: Option<&String>,
+    text: &str,
+    format: &XmlFormat,
+) -> Result<Map<String, Value>, Box<dyn Error + Send + Sync>> {
+    let index = LineIndex::new(text);
+    let mut reader = Reader::from_str(text);
+    let error = |position: u64, message: String| XmlError {
+        location: index.locate(position as usize..position as usize),
+        message,
+    };
+
+    let mut stack: Vec<Element> = Vec::new();
+    let mut root = None;
+    loop {
+        let start = reader.buffer_position();
+        let event = reader
+            .read_event()
+            .map_err(|e| error(reader.error_position(), e.to_string()))?;
+        let location = index.locate(start as usize..reader.buffer_position() as usize);
+
+        let element = match event {
+            Event::Start(ref e) | Event::Empty(ref e) => {
+                let element = start_element(uri, e, location, format)
+                    .map_err(|e| error(start, e.to_string()))?;
+                if matches!(event, Event::Start(_)) {
+                    stack.push(element);
+                    continue;
+                }
+                element
+            }
+            Event::End(_) => match stack.pop() {
+                Some(element) => element,
+                None => continue,
+            },
+            Event::Text(ref e) => {
+                if let Some(element) = stack.last_mut() {
+                    let text = e.unescape().map_err(|e| error(start, e.to_string()))?;
+                    element.text.push_str(&text);
+                }
+                continue;
+            }
+            Event::CData(ref e) => {
+                if let Some(element) = stack.last_mut() {
+                    let text = e.decode().map_err(|e| error(start, e.to_string()))?;
+                    element.text.push_str(&text);
+                }
+                continue;
+            }
+            Event::Eof => match stack.last() {
+                Some(element) => {
+                    return Err(Box::new(error(
+                        start,
+                        format!("unclosed element `{}`", element.name),
+                    )));
+                }
+                None => break,
+            },
+            _ => continue,
+        };
+
+        let name = element.name.clone();
+        let location = element.location.clone();
+        let value = finish_element(uri, element, format)?;
+        match stack.last_mut() {
+            Some(parent) => insert(&mut parent.table, name, value),
+            None if root.is_some() => {
+                return Err(Box::new(XmlError {
+                    location,
+                    message: format!("`{name}` is a second root element"),
+                }));
+            }
+            None if matches!(value.kind, ValueKind::String(ref text) if !text.is_empty()) => {
+                return Err(Box::new(XmlError {
+                    location,
+                    message: format!("the root element `{name}` holds text rather than a table"),
+                }));
+            }
+            None => root = Some(value),
+        }
+    }
+
+    match root.map(|root| root.kind) {
+        Some(ValueKind::Table(table)) => Ok(table),
+        _ => Ok(Map::new()),
+    }
+}
+
+fn start_element(
+    uri: Option<&String>,
+    start: &BytesStart<'_>,
+    location: Location,
+    format: &XmlFormat,
+) -> Result<Element, quick_xml::Error> {
+    let mut attributes = Map::new();
+    for attribute in start.attributes() {
+        let attribute = attribute?;
+        let key = format!(
+            "{}{}",
+            format.attribute_prefix,
+            String::from_utf8_lossy(attribute.key.as_ref())
+        );
+        let mut value = Value::new(uri, attribute.unescape_value()?.into_owned());
+        value.location = Some(location.clone());
+        attributes.insert(key, value);
+    }
+
+    Ok(Element {
+        name: String::from_utf8_lossy(start.name().as_ref()).into_owned(),
+        attributes,
+        table: Map::new(),
+        text: String::new(),
+        location,
+    })
+}
+
+fn finish_element(
+    uri: Option<&String>,
+    element: Element,
+    format: &XmlFormat,
+) -> Result<Value, XmlError> {
+    let Element {
+        name,
+        attributes,
+        mut table,
+        text,
+        location,
+    } = element;
+    let error = |message: String| XmlError {
+        location: location.clone(),
+        message: format!("{message} in element `{name}`"),
+    };
+
+    for (key, attribute) in attributes {
+        if table.contains_key(&key) {
+            return Err(error(format!(
+                "attribute `{key}` has the key of a child element"
+            )));
+        }
+        table.insert(key, attribute);
+    }
+
+    let text = text.trim();
+    let mut value = if table.is_empty() {
+        Value::new(uri, text)
+    } else {
+        if !text.is_empty() {
+            if table.contains_key(&format.text_key) {
+                return Err(error(format!(
+                    "text has the key `{}` of an attribute or a child element",
+                    format.text_key
+                )));
+            }
+            let mut text = Value::new(uri, text);
+            text.location = Some(location.clone());
+            table.insert(format.text_key.clone(), text);
+        }
+        Value::new(uri, table)
+    };
+    value.location = Some(location);
+
+    Ok(value)
+}
+
+/// Inserts `value` at `key`, turning the value already there into an array of both.
+fn insert(table: &mut Map<String, Value>, key: String, value: Value) {
+    match table.get_mut(&key) {
+        Some(existing) => match existing.kind {
+            ValueKind::Array(ref mut items) => items.push(value),
+            _ => {
+                let first = mem::take(existing);
+                let origin = first.origin().map(str::to_owned);
+                *existing = Value::new(origin.as_ref(), vec![first, value]);
+            }
+        },
+        None => {
+            table.insert(key, value);
+        }
+    }
+}
+
+pub(crate) fn render(
+    values: &Map<String, Value>,
+    format: &XmlFormat,
+) -> Result<String, Box<dyn Error + Send + Sync>> {
+    let mut text = String::new();
+    render_element("config", values, 0, format, &mut text)?;
+
+    Ok(text)
+}
+
+fn render_element(
+    name: &str,
+    table: &Map<String, Value>,
+    depth: usize,
+    format: &XmlFormat,
+    text: &mut String,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let mut keys: Vec<&String> = table.keys().collect();
+    keys.sort();
+
+    let indent = "  ".repeat(depth);
+    text.push_str(&format!("{indent}<{name}"));
+    let mut content = None;
+    let mut children = Vec::new();
+    for key in keys {
+        let value = &table[key];
+        let attribute = match key.strip_prefix(format.attribute_prefix.as_str()) {
+            Some(attribute) if !format.attribute_prefix.is_empty() => attribute,
+            _ if *key == format.text_key => {
+                content = Some(scalar(value)?);
+                continue;
+            }
+            _ => {
+                children.push((key, value));
+                continue;
+            }
+        };
+        text.push_str(&format!(
+            " {}=\"{}\"",
+            checked_name(attribute)?,
+            quick_xml::escape::escape(scalar(value)?)
+        ));
+    }
+
+    if children.is_empty() {
+        match content {
+            Some(content) => text.push_str(&format!(">{}</{name}>\n", escape(&content))),
+            None => text.push_str("/>\n"),
+        }
+        return Ok(());
+    }
+
+    text.push_str(">\n");
+    if let Some(content) = content {
+        text.push_str(&format!("{indent}  {}\n", escape(&content)));
+    }
+    for (key, value) in children {
+        let items = match value.kind {
+            ValueKind::Array(ref items) => items.iter().collect(),
+            _ => vec![value],
+        };
+        for item in items {
+            render_value(checked_name(key)?, item, depth + 1, format, text)?;
+        }
+    }
+    text.push_str(&format!("{indent}</{name}>\n"));
+
+    Ok(())
+}
+
+fn render_value(
+    name: &str,
+    value: &Value,
+    depth: usize,
+    format: &XmlFormat,
+    text: &mut String,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    match value.kind {
+        ValueKind::Table(ref table) => render_element(name, table, depth, format, text),
+        ValueKind::Array(_) => Err(Box::new(UnsupportedValueError("nested arrays"))),
+        _ => {
+            let indent = "  ".repeat(depth);
+            text.push_str(&format!(
+                "{indent}<{name}>{}</{name}>\n",
+                escape(&scalar(value)?)
+            ));
+            Ok(())
+        }
+    }
+}
+
+/// The text of a value that is neither a table nor an array.
+fn scalar(value: &Value) -> Result<String, Box<dyn Error + Send + Sync>> {
+    match value.kind {
+        ValueKind::Nil => Ok(String::new()),
+        ValueKind::Table(_) | ValueKind::Array(_) => Err(Box::new(UnsupportedValueError(
+            "tables and arrays as attributes or text",
+        ))),
+        ref kind => Ok(kind.to_string()),
+    }
+}
+
+fn escape(text: &str) -> String {
+    quick_xml::escape::escape(text).into_owned()
+}
+
+/// Checks that `name` can name an element or an attribute.
+fn checked_name(name: &str) -> Result<&str, Box<dyn Error + Send + Sync>> {
+    let mut chars = name.chars();
+    let valid = chars
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_' || c == ':')
+        && chars.all(|c| c.is_alphanumeric() || matches!(c, '_' | ':' | '-' | '.'));
+
+    if valid {
+        Ok(name)
+    } else {
+        Err(format!("`{name}` is not a valid XML name").into())
+    }
+}
+
+/// An error in the syntax of an XML file.
+#[derive(Debug)]
+pub(crate) struct XmlError {
+    pub(crate) location: Location,
+    pub(crate) message: String,
+}
+
+impl fmt::Display for XmlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at line {} column {}",
+            self.message,
+            self.location.line(),
+            self.location.column()
+        )
+    }
+}
+
+impl Error for XmlError {}
+
+#[derive(Debug, Clone)]
+struct UnsupportedValueError(&'static str);
+
+impl fmt::Display for UnsupportedValueError {
+    fn fmt(&self, format: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(format, "XML does not support {}", self.0)
+    }
+}
+
+impl Error for UnsupportedValueError {}
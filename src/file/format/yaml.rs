@@ -1,29 +1,45 @@
+use std::collections::HashMap;
+use std::convert::TryFrom;
 use std::error::Error;
 use std::fmt;
-use std::mem;
 
+use yaml::parser::{Event, MarkedEventReceiver, Parser, Tag};
+use yaml::scanner::{Marker, ScanError, TScalarStyle};
 use yaml_rust2 as yaml;
 
 use crate::format;
 use crate::map::Map;
-use crate::value::{Value, ValueKind};
+use crate::merge::DELETE;
+use crate::value::{Location, Value, ValueKind};
 
 pub(crate) fn parse(
     uri: Option<&String>,
     text: &str,
 ) -> Result<Map<String, Value>, Box<dyn Error + Send + Sync>> {
-    // Parse a YAML object from file
-    let mut docs = yaml::YamlLoader::load_from_str(text)?;
-    let root = match docs.len() {
-        0 => yaml::Yaml::Hash(yaml::yaml::Hash::new()),
-        1 => mem::replace(&mut docs[0], yaml::Yaml::Null),
+    // Parse a YAML object from file, locating its values as they are read
+    let mut loader = Loader::new(uri);
+    Parser::new_from_str(text).load(&mut loader, true)?;
+    if let Some(error) = loader.error {
+        return Err(error);
+    }
+
+    let root = match loader.docs.len() {
+        0 => Value::new(uri, Map::<String, Value>::new()),
+        1 => loader.docs.remove(0),
         n => {
             return Err(Box::new(MultipleDocumentsError(n)));
         }
     };
 
-    let value = from_yaml_value(uri, &root)?;
-    format::extract_root_table(uri, value)
+    format::extract_root_table(uri, root)
+}
+
+pub(crate) fn render(values: &Map<String, Value>) -> Result<String, Box<dyn Error + Send + Sync>> {
+    let mut text = String::new();
+    let root = to_yaml_hash(values)?;
+    yaml::YamlEmitter::new(&mut text).dump(&root)?;
+    text.push('\n');
+    Ok(text)
 }
 
 fn from_yaml_value(
@@ -32,45 +48,237 @@ fn from_yaml_value(
 ) -> Result<Value, Box<dyn Error + Send + Sync>> {
     match *value {
         yaml::Yaml::String(ref value) => Ok(Value::new(uri, ValueKind::String(value.clone()))),
-        yaml::Yaml::Real(ref value) => {
-            // TODO: Figure out in what cases this can panic?
+        yaml::Yaml::Real(ref real) => {
+            // Reads `.inf`, `-.inf` and `.nan` as well
             value
-                .parse::<f64>()
-                .map_err(|_| {
-                    Box::new(FloatParsingError(value.to_string())) as Box<(dyn Error + Send + Sync)>
+                .as_f64()
+                .ok_or_else(|| {
+                    Box::new(FloatParsingError(real.to_string())) as Box<dyn Error + Send + Sync>
                 })
                 .map(ValueKind::Float)
                 .map(|f| Value::new(uri, f))
         }
         yaml::Yaml::Integer(value) => Ok(Value::new(uri, ValueKind::I64(value))),
         yaml::Yaml::Boolean(value) => Ok(Value::new(uri, ValueKind::Boolean(value))),
-        yaml::Yaml::Hash(ref table) => {
-            let mut m = Map::new();
-            for (key, value) in table {
-                match key {
-                    yaml::Yaml::String(k) => m.insert(k.to_owned(), from_yaml_value(uri, value)?),
-                    yaml::Yaml::Integer(k) => m.insert(k.to_string(), from_yaml_value(uri, value)?),
-                    _ => unreachable!(),
+
+        // 1. Yaml NULL
+        // 2. BadValue – It shouldn't be possible to hit BadValue as this only happens when
+        //               using the index trait badly or on a type error but we send back nil.
+        // 3. Hash and Array – The loader builds those out of the events of the parser
+        _ => Ok(Value::new(uri, ValueKind::Nil)),
+    }
+}
+
+fn to_yaml_hash(table: &Map<String, Value>) -> Result<yaml::Yaml, Box<dyn Error + Send + Sync>> {
+    let mut hash = yaml::yaml::Hash::new();
+    for (key, value) in table {
+        hash.insert(yaml::Yaml::String(key.clone()), to_yaml_value(value)?);
+    }
+    Ok(yaml::Yaml::Hash(hash))
+}
+
+fn to_yaml_value(value: &Value) -> Result<yaml::Yaml, Box<dyn Error + Send + Sync>> {
+    Ok(match value.kind {
+        ValueKind::Nil => yaml::Yaml::Null,
+        ValueKind::Boolean(value) => yaml::Yaml::Boolean(value),
+        ValueKind::I64(value) => yaml::Yaml::Integer(value),
+        ValueKind::I128(value) => to_yaml_integer(value)?,
+        ValueKind::U64(value) => to_yaml_integer(value)?,
+        ValueKind::U128(value) => to_yaml_integer(value)?,
+        ValueKind::Float(value) if value.is_nan() => yaml::Yaml::Real(".nan".to_owned()),
+        ValueKind::Float(value) if value.is_infinite() => {
+            yaml::Yaml::Real(if value > 0.0 { ".inf" } else { "-.inf" }.to_owned())
+        }
+        // Debug keeps the fractional part, so the value is read back as a float
+        ValueKind::Float(value) => yaml::Yaml::Real(format!("{value:?}")),
+        ValueKind::String(ref value) => yaml::Yaml::String(value.clone()),
+        ValueKind::Table(ref table) => to_yaml_hash(table)?,
+        ValueKind::Array(ref array) => {
+            yaml::Yaml::Array(array.iter().map(to_yaml_value).collect::<Result<_, _>>()?)
+        }
+    })
+}
+
+/// Converts an integer YAML can hold, those are read as `i64`.
+fn to_yaml_integer<T>(value: T) -> Result<yaml::Yaml, Box<dyn Error + Send + Sync>>
+where
+    T: Copy + fmt::Display,
+    i64: TryFrom<T>,
+{
+    i64::try_from(value)
+        .map(yaml::Yaml::Integer)
+        .map_err(|_| Box::new(IntegerRangeError(value.to_string())) as _)
+}
+
+/// Resolves a scalar the way `YamlLoader` does.
+fn resolve_scalar(value: String, style: TScalarStyle, tag: Option<&Tag>) -> yaml::Yaml {
+    if style != TScalarStyle::Plain {
+        return yaml::Yaml::String(value);
+    }
+
+    match tag {
+        Some(tag) if tag.handle == "tag:yaml.org,2002:" => match tag.suffix.as_ref() {
+            "bool" => value
+                .parse::<bool>()
+                .map_or(yaml::Yaml::BadValue, yaml::Yaml::Boolean),
+            "int" => value
+                .parse::<i64>()
+                .map_or(yaml::Yaml::BadValue, yaml::Yaml::Integer),
+            "float" => match yaml::Yaml::Real(value) {
+                real if real.as_f64().is_some() => real,
+                _ => yaml::Yaml::BadValue,
+            },
+            "null" => match value.as_ref() {
+                "~" | "null" => yaml::Yaml::Null,
+                _ => yaml::Yaml::BadValue,
+            },
+            _ => yaml::Yaml::String(value),
+        },
+        Some(_) => yaml::Yaml::String(value),
+        // Datatype is not specified
+        None => yaml::Yaml::from_str(&value),
+    }
+}
+
+enum Node {
+    /// A mapping, with the key of the value being read once its key was read.
+    Mapping(Map<String, Value>, Option<String>),
+    Sequence(Vec<Value>),
+}
+
+/// Builds the values of the documents of a YAML text out of the events of its parser, so that
+/// each value gets the location it was read at.
+struct Loader<'a> {
+    uri: Option<&'a String>,
+    documents: usize,
+    docs: Vec<Value>,
+    stack: Vec<(Node, usize, Location)>,
+    anchors: HashMap<usize, Value>,
+    error: Option<Box<dyn Error + Send + Sync>>,
+}
+
+impl<'a> Loader<'a> {
+    fn new(uri: Option<&'a String>) -> Self {
+        Self {
+            uri,
+            documents: 0,
+            docs: Vec::new(),
+            stack: Vec::new(),
+            anchors: HashMap::new(),
+            error: None,
+        }
+    }
+
+    fn on_event_impl(
+        &mut self,
+        event: Event,
+        mark: Marker,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let location = Location::new(mark.line(), mark.col() + 1);
+
+        match event {
+            Event::DocumentEnd => {
+                // An empty document holds nothing
+                self.documents += 1;
+                if self.docs.len() < self.documents {
+                    self.docs.push(Value::new(self.uri, ValueKind::Nil));
+                }
+            }
+            Event::MappingStart(anchor, _) => {
+                self.stack
+                    .push((Node::Mapping(Map::new(), None), anchor, location));
+            }
+            Event::SequenceStart(anchor, _) => {
+                self.stack
+                    .push((Node::Sequence(Vec::new()), anchor, location));
+            }
+            Event::MappingEnd | Event::SequenceEnd => {
+                if let Some((node, anchor, location)) = self.stack.pop() {
+                    let kind = match node {
+                        Node::Mapping(table, _) => ValueKind::Table(table),
+                        Node::Sequence(array) => ValueKind::Array(array),
+                    };
+                    let mut value = Value::new(self.uri, kind);
+                    value.location = Some(location);
+                    self.insert(value, anchor, mark)?;
+                }
+            }
+            Event::Scalar(scalar, style, anchor, tag) => {
+                if let Some((Node::Mapping(_, key @ None), ..)) = self.stack.last_mut() {
+                    // Keys are read as strings, `1: x` has the key `1`
+                    *key = Some(match resolve_scalar(scalar.clone(), style, tag.as_ref()) {
+                        yaml::Yaml::Integer(k) => k.to_string(),
+                        _ => scalar,
+                    });
+                    return Ok(());
+                }
+
+                let delete = tag
+                    .as_ref()
+                    .is_some_and(|tag| tag.handle == "!" && tag.suffix == "delete");
+                let mut value = if delete {
+                    Value::new(self.uri, DELETE)
+                } else {
+                    from_yaml_value(self.uri, &resolve_scalar(scalar, style, tag.as_ref()))?
                 };
+                value.location = Some(location);
+                self.insert(value, anchor, mark)?;
             }
-            Ok(Value::new(uri, ValueKind::Table(m)))
+            Event::Alias(anchor) => {
+                let mut value = self
+                    .anchors
+                    .get(&anchor)
+                    .cloned()
+                    .unwrap_or_else(|| Value::new(self.uri, ValueKind::Nil));
+                value.location = Some(location);
+                self.insert(value, 0, mark)?;
+            }
+            _ => {}
         }
-        yaml::Yaml::Array(ref array) => {
-            let mut l = Vec::new();
 
-            for value in array {
-                l.push(from_yaml_value(uri, value)?);
-            }
+        Ok(())
+    }
 
-            Ok(Value::new(uri, ValueKind::Array(l)))
+    fn insert(
+        &mut self,
+        value: Value,
+        anchor: usize,
+        mark: Marker,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        // Valid anchor ids start from 1
+        if anchor > 0 {
+            self.anchors.insert(anchor, value.clone());
         }
 
-        // 1. Yaml NULL
-        // 2. BadValue – It shouldn't be possible to hit BadValue as this only happens when
-        //               using the index trait badly or on a type error but we send back nil.
-        // 3. Alias – No idea what to do with this and there is a note in the lib that its
-        //            not fully supported yet anyway
-        _ => Ok(Value::new(uri, ValueKind::Nil)),
+        match self.stack.last_mut() {
+            Some((Node::Sequence(array), ..)) => array.push(value),
+            Some((Node::Mapping(table, key), ..)) => match key.take() {
+                Some(key) => {
+                    if table.contains_key(&key) {
+                        return Err(Box::new(ScanError::new_string(
+                            mark,
+                            format!("{key:?}: duplicated key in mapping"),
+                        )));
+                    }
+                    table.insert(key, value);
+                }
+                None => return Err(Box::new(UnsupportedKeyError(mark))),
+            },
+            None => self.docs.push(value),
+        }
+
+        Ok(())
+    }
+}
+
+impl MarkedEventReceiver for Loader<'_> {
+    fn on_event(&mut self, event: Event, mark: Marker) {
+        if self.error.is_some() {
+            return;
+        }
+        if let Err(error) = self.on_event_impl(event, mark) {
+            self.error = Some(error);
+        }
     }
 }
 
@@ -89,6 +297,37 @@ impl Error for MultipleDocumentsError {
     }
 }
 
+#[derive(Debug, Clone)]
+struct UnsupportedKeyError(Marker);
+
+impl fmt::Display for UnsupportedKeyError {
+    fn fmt(&self, format: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            format,
+            "Keys of YAML mappings must be scalars, at line {} column {}",
+            self.0.line(),
+            self.0.col() + 1
+        )
+    }
+}
+
+impl Error for UnsupportedKeyError {}
+
+#[derive(Debug, Clone)]
+struct IntegerRangeError(String);
+
+impl fmt::Display for IntegerRangeError {
+    fn fmt(&self, format: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            format,
+            "YAML does not support integers out of the range of i64, such as {}",
+            self.0
+        )
+    }
+}
+
+impl Error for IntegerRangeError {}
+
 #[derive(Debug, Clone)]
 struct FloatParsingError(String);
 
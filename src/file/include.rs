@@ -0,0 +1,175 @@
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::{ConfigError, Result};
+use crate::file::source::file::FileSourceFile;
+use crate::file::{FileFormat, FileSource};
+use crate::map::{self, Map};
+use crate::value::{Value, ValueKind};
+
+/// Lists the layers of `map`, parsed from the file at `uri`: the files named under the include
+/// `key`, in the order they are listed, then `map` itself without the key.
+///
+/// Each file is a layer of its own, merged like sources are. `chain` holds the files including
+/// this one, to detect cycles. The included files are added to `included`, along with the
+/// directories the patterns with wildcards look into.
+pub(crate) fn resolve_includes(
+    key: &str,
+    uri: Option<&String>,
+    mut map: Map<String, Value>,
+    chain: &mut Vec<PathBuf>,
+    included: &mut Vec<PathBuf>,
+) -> Result<Vec<Map<String, Value>>> {
+    let Some(include) = map::remove(&mut map, key) else {
+        return Ok(vec![map]);
+    };
+
+    let patterns = match include.kind {
+        ValueKind::Array(patterns) => patterns
+            .into_iter()
+            .map(Value::into_string)
+            .collect::<Result<_>>(),
+        _ => include.into_string().map(|pattern| vec![pattern]),
+    }
+    .map_err(|e| e.extend_with_key(key))?;
+
+    // Paths are relative to the including file
+    let base = uri
+        .and_then(|uri| Path::new(uri).parent())
+        .unwrap_or_else(|| Path::new(""));
+    let fail = |cause: Box<dyn Error + Send + Sync>| ConfigError::FileParse {
+        uri: uri.cloned(),
+        location: None,
+        cause,
+    };
+
+    let mut layers = Vec::new();
+    for pattern in patterns {
+        let pattern = base.join(&pattern);
+        if let Some(directory) = wildcard_directory(&pattern) {
+            included.push(directory.to_path_buf());
+        }
+        for path in expand(&pattern).map_err(fail)? {
+            included.push(path.clone());
+            let layer = include_file(key, &path, chain, included).map_err(|error| {
+                fail(Box::new(IncludeError {
+                    error: Box::new(error),
+                    from_string: uri.is_none(),
+                }))
+            })?;
+            layers.extend(layer);
+        }
+    }
+    layers.push(map);
+
+    Ok(layers)
+}
+
+fn include_file(
+    key: &str,
+    path: &Path,
+    chain: &mut Vec<PathBuf>,
+    included: &mut Vec<PathBuf>,
+) -> Result<Vec<Map<String, Value>>> {
+    let source = FileSourceFile::new(path.to_path_buf());
+    let result = FileSource::<FileFormat>::resolve(&source, None).map_err(ConfigError::Foreign)?;
+
+    // The path may leave the extension out, look at the file that was found
+    let found = result.uri.as_deref().map_or(path, Path::new);
+    let canonical = fs::canonicalize(found).map_err(|e| ConfigError::Foreign(Box::new(e)))?;
+    if chain.contains(&canonical) {
+        let base = env::current_dir().unwrap_or_default();
+        let cycle: Vec<_> = chain
+            .iter()
+            .chain(Some(&canonical))
+            .map(|path| {
+                let path = pathdiff::diff_paths(path, &base).unwrap_or_else(|| path.clone());
+                path.to_string_lossy().into_owned()
+            })
+            .collect();
+        return Err(ConfigError::Message(format!(
+            "include cycle: {}",
+            cycle.join(" -> ")
+        )));
+    }
+
+    let map = super::parse(result.format.as_ref(), result.uri.as_ref(), &result.content)?;
+
+    chain.push(canonical);
+    let layers = resolve_includes(key, result.uri.as_ref(), map, chain, included);
+    chain.pop();
+
+    layers
+}
+
+/// The directory holding the files matching `pattern`, if its file name has wildcards but not
+/// its directory.
+fn wildcard_directory(pattern: &Path) -> Option<&Path> {
+    let directory = pattern.parent()?;
+    if !has_wildcards(Path::new(pattern.file_name()?)) || has_wildcards(directory) {
+        return None;
+    }
+
+    Some(directory)
+}
+
+fn has_wildcards(path: &Path) -> bool {
+    path.to_string_lossy().contains(['*', '?', '['])
+}
+
+/// Lists the files matching `pattern`, in alphabetical order.
+///
+/// A pattern without wildcards names a single file, which must exist.
+fn expand(pattern: &Path) -> std::result::Result<Vec<PathBuf>, Box<dyn Error + Send + Sync>> {
+    if !has_wildcards(pattern) {
+        return Ok(vec![pattern.to_path_buf()]);
+    }
+    let text = pattern.to_string_lossy();
+
+    #[cfg(feature = "glob")]
+    {
+        let mut paths = Vec::new();
+        for entry in glob::glob(&text)? {
+            let path = entry?;
+            if path.is_file() {
+                paths.push(path);
+            }
+        }
+
+        Ok(paths)
+    }
+
+    #[cfg(not(feature = "glob"))]
+    Err(format!("including {text} requires the `glob` feature").into())
+}
+
+/// An error in a file included by another one.
+///
+/// It is the cause of a [`ConfigError::FileParse`] whose URI is the including file, if any.
+#[derive(Debug)]
+pub(crate) struct IncludeError {
+    pub(crate) error: Box<ConfigError>,
+
+    /// Whether the including file is a string source, without a URI.
+    pub(crate) from_string: bool,
+}
+
+impl fmt::Display for IncludeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.from_string {
+            write!(f, "{}, included from a string source", self.error)
+        } else {
+            // The URI of the including file follows
+            write!(f, "{}, included", self.error)
+        }
+    }
+}
+
+impl Error for IncludeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(self.error.as_ref())
+    }
+}
@@ -0,0 +1,140 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use crate::env::parse_scalar;
+use crate::error::{ConfigError, Result};
+use crate::map::Map;
+use crate::source::Source;
+use crate::value::{Value, ValueKind};
+
+/// A configuration source reading one value per file, with the name of the file as key.
+///
+/// This is how Kubernetes `ConfigMap`s and `Secret`s, Docker secrets (`/run/secrets`) and systemd
+/// credentials (`$CREDENTIALS_DIRECTORY`) are exposed. A single trailing newline is trimmed from
+/// the values. Subdirectories and files whose name starts with a dot, such as the `..data`
+/// directory Kubernetes creates, are ignored. The whole directory is watched by a
+/// `ReloadableConfig`, so that Kubernetes swapping `..data` for the new files reloads them.
+///
+/// ```rust,no_run
+/// # use config::*;
+/// # fn main() -> Result<(), ConfigError> {
+/// // /run/secrets/db__password holds `hunter2`
+/// let config = Config::builder()
+///     .add_source(KeyPerFile::new("/run/secrets").separator("__"))
+///     .build()?;
+///
+/// assert_eq!(config.get_string("db.password")?, "hunter2");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct KeyPerFile {
+    path: PathBuf,
+
+    /// Character sequence of the file names separating the keys of nested tables
+    separator: Option<String>,
+
+    /// Parses booleans and numbers rather than reading every value as a string
+    try_parsing: bool,
+
+    /// A required directory will error if it cannot be found
+    required: bool,
+}
+
+impl KeyPerFile {
+    /// Reads the files of the directory at `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            separator: None,
+            try_parsing: false,
+            required: true,
+        }
+    }
+
+    /// Nests the values of the files whose name holds `s`, so that with `__` the file
+    /// `db__password` sets `db.password`. Dots in the names nest the values as well.
+    pub fn separator(mut self, s: &str) -> Self {
+        self.separator = Some(s.into());
+        self
+    }
+
+    /// Parses the values holding a boolean, an integer or a float, like
+    /// [`Environment::try_parsing`](crate::Environment::try_parsing) does.
+    pub fn try_parsing(mut self, try_parsing: bool) -> Self {
+        self.try_parsing = try_parsing;
+        self
+    }
+
+    /// Set required to false to ignore the directory if it does not exist when building the
+    /// config.
+    pub fn required(mut self, required: bool) -> Self {
+        self.required = required;
+        self
+    }
+}
+
+impl Source for KeyPerFile {
+    fn clone_into_box(&self) -> Box<dyn Source + Send + Sync> {
+        Box::new((*self).clone())
+    }
+
+    fn describe(&self) -> String {
+        format!("key-per-file directory \"{}\"", self.path.to_string_lossy())
+    }
+
+    fn watch_paths(&self) -> Vec<PathBuf> {
+        vec![self.path.clone()]
+    }
+
+    fn collect(&self) -> Result<Map<String, Value>> {
+        let entries = match fs::read_dir(&self.path) {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == io::ErrorKind::NotFound && !self.required => {
+                return Ok(Map::new());
+            }
+            Err(error) => return Err(ConfigError::Foreign(Box::new(error))),
+        };
+
+        let mut m = Map::new();
+        for entry in entries {
+            let path = entry.map_err(|e| ConfigError::Foreign(Box::new(e)))?.path();
+            let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            // Follows symbolic links, which Kubernetes creates for every key
+            if name.starts_with('.') || !path.is_file() {
+                continue;
+            }
+
+            let uri = path.to_string_lossy().into_owned();
+
+            let mut value = fs::read_to_string(&path).map_err(|e| ConfigError::FileParse {
+                uri: Some(uri.clone()),
+                location: None,
+                cause: Box::new(e),
+            })?;
+            if value.ends_with('\n') {
+                value.pop();
+                if value.ends_with('\r') {
+                    value.pop();
+                }
+            }
+
+            let key = match self.separator.as_deref() {
+                Some(separator) if !separator.is_empty() => name.replace(separator, "."),
+                _ => name.to_owned(),
+            };
+            let value = if self.try_parsing {
+                parse_scalar(value)
+            } else {
+                ValueKind::String(value)
+            };
+            m.insert(key, Value::new(Some(&uri), value));
+        }
+
+        Ok(m)
+    }
+}
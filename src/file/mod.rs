@@ -1,17 +1,28 @@
-mod format;
+mod directory;
+pub(crate) mod format;
+mod include;
+mod key_per_file;
 pub(crate) mod source;
 
 use std::fmt::Debug;
+use std::fs;
 use std::path::{Path, PathBuf};
 
 use self::source::FileSource;
 use crate::error::{ConfigError, Result};
 use crate::map::Map;
-use crate::source::Source;
-use crate::value::Value;
+use crate::merge::{self, ArrayMerges};
+use crate::source::{set_value, Source};
+use crate::value::{Value, ValueKind};
 use crate::Format;
 
+pub use self::directory::{Directory, DirectoryOrder};
 pub use self::format::FileFormat;
+#[cfg(feature = "xml")]
+pub use self::format::XmlFormat;
+#[cfg(feature = "diagnostics")]
+pub(crate) use self::include::IncludeError;
+pub use self::key_per_file::KeyPerFile;
 pub use self::source::file::FileSourceFile;
 pub use self::source::string::FileSourceString;
 
@@ -28,6 +39,9 @@ pub struct File<T, F> {
 
     /// A required File will error if it cannot be found
     required: bool,
+
+    /// Key listing the files to include, if any
+    include: Option<String>,
 }
 
 /// An extension of [`Format`] trait.
@@ -46,6 +60,7 @@ where
         Self {
             format: Some(format),
             required: true,
+            include: None,
             source: s.into(),
         }
     }
@@ -59,6 +74,7 @@ where
         Self {
             format: Some(format),
             required: true,
+            include: None,
             source: FileSourceFile::new(name.into()),
         }
     }
@@ -71,6 +87,7 @@ impl File<FileSourceFile, FileFormat> {
         Self {
             format: None,
             required: true,
+            include: None,
             source: FileSourceFile::new(name.into()),
         }
     }
@@ -81,6 +98,7 @@ impl<'a> From<&'a Path> for File<FileSourceFile, FileFormat> {
         Self {
             format: None,
             required: true,
+            include: None,
             source: FileSourceFile::new(path.to_path_buf()),
         }
     }
@@ -91,6 +109,7 @@ impl From<PathBuf> for File<FileSourceFile, FileFormat> {
         Self {
             format: None,
             required: true,
+            include: None,
             source: FileSourceFile::new(path),
         }
     }
@@ -111,6 +130,24 @@ where
         self.required = required;
         self
     }
+
+    /// Reads the files listed under the top-level `key` as part of this one.
+    ///
+    /// The key holds a path or an array of paths, relative to the file, which may use glob
+    /// patterns such as `secrets/*.yaml` with the `glob` feature. Included files are merged in
+    /// order, then the values of the file itself on top of them, as if each was a source of its
+    /// own. They may include files as well, but not circularly.
+    ///
+    /// ```toml
+    /// include = ["common.toml", "secrets/*.yaml"]
+    ///
+    /// [server]
+    /// port = 8080
+    /// ```
+    pub fn include_key(mut self, key: &str) -> Self {
+        self.include = Some(key.into());
+        self
+    }
 }
 
 impl<T, F> Source for File<T, F>
@@ -122,7 +159,62 @@ where
         Box::new((*self).clone())
     }
 
+    fn describe(&self) -> String {
+        self.source.describe()
+    }
+
     fn collect(&self) -> Result<Map<String, Value>> {
+        let layers = self.collect_files(&mut Vec::new())?;
+        if layers.len() <= 1 {
+            return Ok(layers.into_iter().next().unwrap_or_default());
+        }
+
+        // Merge the included files like sources, with the default strategies
+        let mut tree: Value = Map::<String, Value>::new().into();
+        for layer in layers {
+            merge::merge("", &mut tree, layer.into(), &ArrayMerges::default());
+        }
+        match tree.kind {
+            ValueKind::Table(map) => Ok(map),
+            _ => unreachable!(),
+        }
+    }
+
+    fn collect_layers(&self) -> Result<Vec<Value>> {
+        Ok(self
+            .collect_files(&mut Vec::new())?
+            .into_iter()
+            .map(|map| {
+                let mut tree: Value = Map::<String, Value>::new().into();
+                for (key, val) in &map {
+                    set_value(&mut tree, key, val);
+                }
+                tree
+            })
+            .collect())
+    }
+
+    fn watch_paths(&self) -> Vec<PathBuf> {
+        let mut paths = self.source.watch_paths();
+        if self.include.is_some() {
+            // The included files are only known once the file is read
+            let _ = self.collect_files(&mut paths);
+        }
+
+        paths
+    }
+}
+
+impl<T, F> File<T, F>
+where
+    F: FileStoredFormat + Debug + Clone + Send + Sync + 'static,
+    T: Sync + Send + FileSource<F> + 'static,
+{
+    /// Reads the file, then the files it includes, see [`include_key`](Self::include_key).
+    ///
+    /// Returns the included files first, then the file itself. The paths of the included files
+    /// are added to `included`, as listed by [`Source::watch_paths`].
+    fn collect_files(&self, included: &mut Vec<PathBuf>) -> Result<Vec<Map<String, Value>>> {
         // Coerce the file contents to a string
         let (uri, contents, format) = match self
             .source
@@ -133,7 +225,7 @@ where
 
             Err(error) => {
                 if !self.required {
-                    return Ok(Map::new());
+                    return Ok(Vec::new());
                 }
 
                 return Err(error);
@@ -141,8 +233,37 @@ where
         };
 
         // Parse the string using the given format
-        format
-            .parse(uri.as_ref(), &contents)
-            .map_err(|cause| ConfigError::FileParse { uri, cause })
+        let map = parse(format.as_ref(), uri.as_ref(), &contents)?;
+
+        match self.include {
+            Some(ref key) => {
+                let mut chain: Vec<_> = uri.iter().flat_map(fs::canonicalize).collect();
+                include::resolve_includes(key, uri.as_ref(), map, &mut chain, included)
+            }
+            None => Ok(vec![map]),
+        }
+    }
+}
+
+/// Parses `text` with `format`, keeping the text with the locations of its values for
+/// diagnostics.
+pub(crate) fn parse<F: Format + ?Sized>(
+    format: &F,
+    uri: Option<&String>,
+    text: &str,
+) -> Result<Map<String, Value>> {
+    #[cfg_attr(not(feature = "diagnostics"), allow(unused_mut))]
+    let mut map = format
+        .parse(uri, text)
+        .map_err(|cause| ConfigError::file_parse(uri.cloned(), text, cause))?;
+
+    #[cfg(feature = "diagnostics")]
+    {
+        let source = std::sync::Arc::new(text.to_owned());
+        for value in map.values_mut() {
+            value.attach_source(&source);
+        }
     }
+
+    Ok(map)
 }
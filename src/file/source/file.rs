@@ -5,7 +5,8 @@ use std::io;
 use std::path::PathBuf;
 
 use crate::file::{
-    format::all_extensions, source::FileSourceResult, FileSource, FileStoredFormat, Format,
+    format::all_extensions, source::FileSourceResult, FileFormat, FileSource, FileStoredFormat,
+    Format,
 };
 
 /// Describes a file sourced from a file
@@ -38,16 +39,9 @@ impl FileSourceFile {
             return if let Some(format) = format_hint {
                 Ok((filename, Box::new(format)))
             } else {
-                for (format, extensions) in all_extensions().iter() {
-                    if extensions.contains(
-                        &filename
-                            .extension()
-                            .unwrap_or_default()
-                            .to_string_lossy()
-                            .as_ref(),
-                    ) {
-                        return Ok((filename, Box::new(*format)));
-                    }
+                let extension = filename.extension().unwrap_or_default().to_string_lossy();
+                if let Some(format) = FileFormat::from_extension(&extension) {
+                    return Ok((filename, Box::new(format)));
                 }
 
                 Err(Box::new(io::Error::new(
@@ -123,6 +117,15 @@ where
             format,
         })
     }
+
+    fn describe(&self) -> String {
+        format!("file \"{}\"", self.name.to_string_lossy())
+    }
+
+    fn watch_paths(&self) -> Vec<PathBuf> {
+        // Whichever extension it is found with
+        vec![self.name.clone()]
+    }
 }
 
 fn add_dummy_extension(mut filename: PathBuf) -> PathBuf {
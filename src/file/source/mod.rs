@@ -3,6 +3,7 @@ pub(crate) mod string;
 
 use std::error::Error;
 use std::fmt::Debug;
+use std::path::PathBuf;
 
 use crate::{file::FileStoredFormat, Format};
 
@@ -15,6 +16,21 @@ where
         &self,
         format_hint: Option<T>,
     ) -> Result<FileSourceResult, Box<dyn Error + Send + Sync>>;
+
+    /// Describes where the file is sourced, see [`Source::describe`](crate::Source::describe).
+    ///
+    /// Defaults to the `Debug` output of the file source.
+    fn describe(&self) -> String {
+        format!("{self:?}")
+    }
+
+    /// Lists the files the file is read from, see
+    /// [`Source::watch_paths`](crate::Source::watch_paths).
+    ///
+    /// Defaults to none.
+    fn watch_paths(&self) -> Vec<PathBuf> {
+        Vec::new()
+    }
 }
 
 pub struct FileSourceResult {
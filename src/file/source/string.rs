@@ -30,4 +30,8 @@ where
             format: Box::new(format_hint.expect("from_str requires a set file format")),
         })
     }
+
+    fn describe(&self) -> String {
+        "string".to_owned()
+    }
 }
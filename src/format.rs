@@ -24,6 +24,17 @@ pub trait Format {
     ) -> Result<Map<String, Value>, Box<dyn Error + Send + Sync>>;
 }
 
+/// Describes a format configuration data can be written in
+///
+/// This is the counterpart of [`Format`]: implementations render configuration values back into text,
+/// for instance to generate starter configuration files or to dump the merged configuration.
+///
+/// See [`Config::to_string_as`](crate::Config::to_string_as).
+pub trait FormatWriter {
+    /// Renders provided configuration values into the text of this format.
+    fn render(&self, values: &Map<String, Value>) -> Result<String, Box<dyn Error + Send + Sync>>;
+}
+
 // Have a proper error fire if the root of a file is ever not a Table
 pub(crate) fn extract_root_table(
     uri: Option<&String>,
@@ -0,0 +1,235 @@
+use std::collections::HashMap;
+use std::env;
+use std::mem;
+
+use crate::error::{ConfigError, Result};
+use crate::path::Expression;
+use crate::value::{Value, ValueKind};
+
+/// Expands the references in the strings of the merged configuration `root`.
+///
+/// - `${key.path}` is replaced by the value at that key, itself expanded
+/// - `${env:NAME}` is replaced by the environment variable `NAME`
+/// - `${reference:-default}` falls back to `default`, which may hold references too, if the
+///   reference is not set
+/// - `$${` is replaced by a literal `${`
+///
+/// A string made of a single reference takes the value referenced as is, keeping its type,
+/// otherwise referenced values must be strings, numbers or booleans.
+pub(crate) fn interpolate(root: &mut Value) -> Result<()> {
+    let mut interpolator = Interpolator {
+        root,
+        resolved: HashMap::new(),
+        resolving: Vec::new(),
+    };
+    let interpolated = interpolator.resolve("", root.clone())?;
+    *root = interpolated;
+
+    Ok(())
+}
+
+struct Interpolator<'a> {
+    root: &'a Value,
+    /// Strings expanded so far, by key.
+    resolved: HashMap<String, Value>,
+    /// Keys of the strings being expanded, to detect cycles.
+    resolving: Vec<String>,
+}
+
+impl Interpolator<'_> {
+    /// Expands the strings in `value`, which is at `key`.
+    fn resolve(&mut self, key: &str, mut value: Value) -> Result<Value> {
+        match value.kind {
+            ValueKind::String(_) => return self.resolve_string(key, value),
+            ValueKind::Table(ref mut table) => {
+                *table = mem::take(table)
+                    .into_iter()
+                    .map(|(child, value)| {
+                        let path = if key.is_empty() {
+                            child.clone()
+                        } else {
+                            format!("{key}.{child}")
+                        };
+                        Ok((child, self.resolve(&path, value)?))
+                    })
+                    .collect::<Result<_>>()?;
+            }
+            ValueKind::Array(ref mut array) => {
+                *array = mem::take(array)
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, value)| self.resolve(&format!("{key}[{index}]"), value))
+                    .collect::<Result<_>>()?;
+            }
+            _ => {}
+        }
+
+        Ok(value)
+    }
+
+    fn resolve_string(&mut self, key: &str, mut value: Value) -> Result<Value> {
+        if let Some(resolved) = self.resolved.get(key) {
+            return Ok(resolved.clone());
+        }
+        if self.resolving.iter().any(|k| k == key) {
+            let mut cycle = self.resolving.clone();
+            cycle.push(key.to_owned());
+            return Err(ConfigError::Message(format!(
+                "interpolation cycle for key `{key}`: {}",
+                cycle.join(" -> ")
+            )));
+        }
+
+        self.resolving.push(key.to_owned());
+        let text = match value.kind {
+            ValueKind::String(ref text) => text.clone(),
+            _ => unreachable!(),
+        };
+        let expanded = self.expand(key, &text);
+        self.resolving.pop();
+
+        let resolved = match expanded? {
+            Expanded::Text(text) => {
+                value.kind = ValueKind::String(text);
+                value
+            }
+            // Values from a default stand where the reference is written
+            Expanded::Value(resolved) if resolved.origin().is_none() => {
+                value.kind = resolved.kind;
+                value
+            }
+            Expanded::Value(resolved) => resolved,
+        };
+        self.resolved.insert(key.to_owned(), resolved.clone());
+
+        Ok(resolved)
+    }
+
+    /// Expands the references in `text`, the string at `key`.
+    fn expand(&mut self, key: &str, text: &str) -> Result<Expanded> {
+        let mut expanded = String::new();
+        let mut rest = text;
+
+        while let Some(start) = rest.find('$') {
+            expanded.push_str(&rest[..start]);
+            rest = &rest[start..];
+
+            if let Some(after) = rest.strip_prefix("$${") {
+                expanded.push_str("${");
+                rest = after;
+            } else if rest.starts_with("${") {
+                let end = closing_brace(rest).ok_or_else(|| {
+                    ConfigError::Message(format!("unclosed `${{` in the value of key `{key}`"))
+                })?;
+                let value = self.reference(key, &rest[2..end])?;
+
+                // A lone reference keeps the type of what it refers to
+                if expanded.is_empty() && end + 1 == rest.len() {
+                    return Ok(Expanded::Value(value));
+                }
+
+                match value.kind {
+                    ValueKind::Table(_) | ValueKind::Array(_) => {
+                        return Err(ConfigError::Message(format!(
+                            "key `{key}` interpolates `{}`, which is not a string, number or boolean",
+                            &rest[..=end]
+                        )));
+                    }
+                    ref kind => expanded.push_str(&kind.to_string()),
+                }
+                rest = &rest[end + 1..];
+            } else {
+                expanded.push('$');
+                rest = &rest[1..];
+            }
+        }
+        expanded.push_str(rest);
+
+        Ok(Expanded::Text(expanded))
+    }
+
+    /// Looks up the `reference` between `${` and `}` in the string at `key`.
+    fn reference(&mut self, key: &str, reference: &str) -> Result<Value> {
+        let (name, default) = match split_default(reference) {
+            Some((name, default)) => (name.trim(), Some(default)),
+            None => (reference.trim(), None),
+        };
+
+        let value = match name.strip_prefix("env:") {
+            Some(var) => env::var(var)
+                .ok()
+                .map(|v| Value::new(Some(&"the environment".to_owned()), v)),
+            None => self.lookup(name)?,
+        };
+
+        match (value, default) {
+            (Some(value), _) if !matches!(value.kind, ValueKind::Nil) => Ok(value),
+            (_, Some(default)) => match self.expand(key, default)? {
+                Expanded::Text(text) => Ok(Value::new(None, text)),
+                Expanded::Value(value) => Ok(value),
+            },
+            _ => Err(ConfigError::Message(format!(
+                "key `{key}` interpolates `${{{reference}}}`, which is not set"
+            ))),
+        }
+    }
+
+    /// The expanded value at `key`, if it is set.
+    fn lookup(&mut self, key: &str) -> Result<Option<Value>> {
+        let expr: Expression = key.parse()?;
+        match expr.get(self.root).cloned() {
+            Some(value) => self.resolve(key, value).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+enum Expanded {
+    Text(String),
+    Value(Value),
+}
+
+/// Finds the `}` closing the `${` that `text` starts with, skipping nested references.
+fn closing_brace(text: &str) -> Option<usize> {
+    let mut depth = 0;
+    let bytes = text.as_bytes();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'$' if bytes.get(i + 1) == Some(&b'{') => {
+                depth += 1;
+                i += 1;
+            }
+            b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    None
+}
+
+/// Splits `name:-default` at the first `:-` outside of nested references.
+fn split_default(reference: &str) -> Option<(&str, &str)> {
+    let mut depth = 0;
+    let bytes = reference.as_bytes();
+
+    for i in 0..bytes.len() {
+        match bytes[i] {
+            b'$' if bytes.get(i + 1) == Some(&b'{') => depth += 1,
+            b'}' => depth -= 1,
+            b':' if depth == 0 && bytes.get(i + 1) == Some(&b'-') => {
+                return Some((&reference[..i], &reference[i + 2..]));
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
@@ -0,0 +1,243 @@
+use std::fmt;
+
+use crate::error::Result;
+use crate::interpolate;
+use crate::map::Map;
+use crate::merge::{self, ArrayMerges};
+use crate::path::Expression;
+use crate::profile::Profile;
+use crate::source::Source;
+use crate::value::Value;
+
+/// A layer of a built [`Config`](crate::Config) that may supply values.
+///
+/// Layers are merged in the order defaults, sources (in the order they were added) and overrides,
+/// each one taking precedence over the layers before it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Layer {
+    /// Values set with [`set_default`](crate::ConfigBuilder::set_default).
+    Default,
+
+    /// Values collected from a source.
+    Source {
+        /// The (zero-based) position the source was added at.
+        index: usize,
+
+        /// The description of the source, see [`Source::describe`](crate::Source::describe).
+        description: String,
+    },
+
+    /// Values set with [`set_override`](crate::ConfigBuilder::set_override).
+    Override,
+}
+
+impl fmt::Display for Layer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Default => write!(f, "default"),
+            Self::Source {
+                ref description, ..
+            } => write!(f, "{description}"),
+            Self::Override => write!(f, "override"),
+        }
+    }
+}
+
+/// A value supplied for a key by a single [`Layer`].
+#[derive(Debug, Clone)]
+pub struct Contribution {
+    layer: Layer,
+    value: Value,
+}
+
+impl Contribution {
+    /// The layer that supplied the value.
+    pub fn layer(&self) -> &Layer {
+        &self.layer
+    }
+
+    /// The value as supplied by the layer.
+    pub fn value(&self) -> &Value {
+        &self.value
+    }
+
+    /// The original location of the value, for instance the file it was read from.
+    pub fn origin(&self) -> Option<&str> {
+        self.value.origin()
+    }
+}
+
+/// Where the value of a key came from, see [`Config::explain`](crate::Config::explain).
+#[derive(Debug, Clone)]
+pub struct Explanation {
+    key: String,
+    value: Value,
+    layers: Vec<Contribution>,
+}
+
+impl Explanation {
+    pub(crate) fn new(
+        key: &str,
+        value: Value,
+        expr: &Expression,
+        layers: &[(Layer, Value)],
+    ) -> Self {
+        let layers = layers
+            .iter()
+            .filter_map(|(layer, tree)| {
+                expr.clone().get(tree).map(|value| Contribution {
+                    layer: layer.clone(),
+                    value: value.clone(),
+                })
+            })
+            .collect();
+
+        Self {
+            key: key.to_owned(),
+            value,
+            layers,
+        }
+    }
+
+    /// The explained key.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// The final value of the key.
+    pub fn value(&self) -> &Value {
+        &self.value
+    }
+
+    /// All values supplied for the key, ordered from lowest to highest precedence.
+    pub fn layers(&self) -> &[Contribution] {
+        &self.layers
+    }
+
+    /// The value of the layer with the highest precedence.
+    pub fn winner(&self) -> Option<&Contribution> {
+        self.layers.last()
+    }
+
+    /// The values the winner shadowed, ordered from lowest to highest precedence.
+    pub fn shadowed(&self) -> &[Contribution] {
+        self.layers
+            .split_last()
+            .map(|(_, shadowed)| shadowed)
+            .unwrap_or_default()
+    }
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} = {}", self.key, self.value)?;
+
+        for (i, contribution) in self.layers.iter().rev().enumerate() {
+            write!(f, "\n  {} from {}", contribution.value, contribution.layer)?;
+            if let Some(origin) = contribution.origin() {
+                write!(f, " ({origin})")?;
+            }
+            if i > 0 {
+                write!(f, ", shadowed")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Merges the layers of a configuration, keeping each of them around if asked to.
+pub(crate) struct LayeredCache {
+    cache: Value,
+    layers: Option<Vec<(Layer, Value)>>,
+    arrays: ArrayMerges,
+    profile: Option<Profile>,
+}
+
+impl LayeredCache {
+    pub(crate) fn new(track_layers: bool) -> Self {
+        Self::with_options(ArrayMerges::default(), None, track_layers)
+    }
+
+    /// A cache merging the arrays of sources as set in `arrays`, and selecting `profile` in
+    /// them if set. The merged layers are only kept with `track_layers`.
+    pub(crate) fn with_options(
+        arrays: ArrayMerges,
+        profile: Option<Profile>,
+        track_layers: bool,
+    ) -> Self {
+        Self {
+            cache: Map::<String, Value>::new().into(),
+            layers: track_layers.then(Vec::new),
+            arrays,
+            profile,
+        }
+    }
+
+    /// Sets values key by key, as done for defaults and overrides.
+    pub(crate) fn set_values<'a, I>(&mut self, layer: Layer, values: I)
+    where
+        I: IntoIterator<Item = (&'a Expression, &'a Value)>,
+    {
+        let mut tree: Value = Map::<String, Value>::new().into();
+
+        for (key, val) in values {
+            if self.layers.is_some() {
+                key.set(&mut tree, val.clone());
+            }
+            if merge::is_delete(val) {
+                key.remove(&mut self.cache);
+            } else {
+                key.set(&mut self.cache, val.clone());
+            }
+        }
+
+        if let Some(ref mut layers) = self.layers {
+            layers.push((layer, tree));
+        }
+    }
+
+    /// Collects the layers of a source, see [`Source::collect_layers`].
+    pub(crate) fn collect_source(
+        &mut self,
+        index: usize,
+        source: &(dyn Source + Send + Sync),
+    ) -> Result<()> {
+        let description = source.describe();
+        for tree in source.collect_layers()? {
+            let layer = Layer::Source {
+                index,
+                description: description.clone(),
+            };
+            self.merge(layer, tree);
+        }
+
+        Ok(())
+    }
+
+    /// Merges an already collected layer on top of the cache.
+    pub(crate) fn merge(&mut self, layer: Layer, mut tree: Value) {
+        if let Some(ref profile) = self.profile {
+            tree = profile.apply(tree, &self.arrays);
+        }
+
+        match self.layers {
+            Some(ref mut layers) => {
+                merge::merge("", &mut self.cache, tree.clone(), &self.arrays);
+                layers.push((layer, tree));
+            }
+            None => merge::merge("", &mut self.cache, tree, &self.arrays),
+        }
+    }
+
+    /// Expands the references in the strings of the merged configuration, the layers keep them
+    /// as written.
+    pub(crate) fn interpolate(&mut self) -> Result<()> {
+        interpolate::interpolate(&mut self.cache)
+    }
+
+    pub(crate) fn into_parts(self) -> (Value, Option<Vec<(Layer, Value)>>) {
+        (self.cache, self.layers)
+    }
+}
@@ -6,14 +6,27 @@
 //!  - Environment variables
 //!  - String literals in well-known formats
 //!  - Another Config instance
-//!  - Files: TOML, JSON, YAML, INI, RON, JSON5 and custom ones defined with Format trait
+//!  - Files: TOML, JSON, YAML, INI, RON, JSON5, `.env`, Java properties, XML, HCL, KDL and custom ones defined with Format trait
 //!  - Manual, programmatic override (via a `.set` method on the Config instance)
 //!
 //! Additionally, Config supports:
 //!
-//!  - Live watching and re-reading of configuration files
+//!  - Live watching and re-reading of configuration files, see `ReloadableConfig` (`watch` feature)
+//!  - Reading every file of a `conf.d` directory, see [`Directory`]
+//!  - Reading one value per file, as Kubernetes and Docker mount secrets, see [`KeyPerFile`]
+//!  - Files including other files, see [`File::include_key`]
+//!  - Appending to or merging into the arrays of earlier sources, see [`ConfigBuilder::array_merge`]
+//!  - Deleting keys set by earlier layers, see [`DELETE`] and [`ConfigBuilder::unset`]
+//!  - Profiles, sections of the sources selected when building, see [`ConfigBuilder::profile`]
+//!  - Filtering, renaming and converting the keys and values of any source, see [`Transform`]
 //!  - Deep access into the merged configuration via a path syntax
+//!  - Expanding `${key}` and `${env:NAME}` references in string values, see [`ConfigBuilder::interpolate`]
+//!  - Tracing back which layer supplied a value, see [`ConfigBuilder::track_layers`] and [`Config::explain`]
+//!  - Comparing two configurations key by key, see [`Config::diff`]
 //!  - Deserialization via `serde` of the configuration or any subset defined via a path
+//!  - Reporting every missing or invalid value at once, see [`Config::try_deserialize_collecting_errors`]
+//!  - Spotting keys the application does not read, see [`Config::try_deserialize_reporting_unused`]
+//!  - Writing the configuration back out in any of the file formats, see [`FormatWriter`]
 //!
 //! See the [examples](https://github.com/mehcode/config-rs/tree/master/examples) for
 //! general usage information.
@@ -25,15 +38,26 @@
 pub mod builder;
 mod config;
 mod de;
+#[cfg(feature = "diagnostics")]
+mod diagnostic;
+mod diff;
 mod env;
 mod error;
 mod file;
 mod format;
+mod interpolate;
+mod layer;
 mod map;
+mod merge;
 mod path;
+mod profile;
 mod ser;
 mod source;
+mod suggest;
+mod transform;
 mod value;
+#[cfg(feature = "watch")]
+mod watch;
 
 // Re-export
 #[cfg(feature = "convert-case")]
@@ -41,13 +65,27 @@ pub use convert_case::Case;
 
 pub use crate::builder::ConfigBuilder;
 pub use crate::config::Config;
+pub use crate::de::UnusedKey;
+#[cfg(feature = "diagnostics")]
+pub use crate::diagnostic::Diagnostic;
+pub use crate::diff::{Diff, DiffEntry, DiffKind};
 pub use crate::env::Environment;
 pub use crate::error::ConfigError;
 pub use crate::file::source::FileSource;
-pub use crate::file::{File, FileFormat, FileSourceFile, FileSourceString, FileStoredFormat};
-pub use crate::format::Format;
+#[cfg(feature = "xml")]
+pub use crate::file::XmlFormat;
+pub use crate::file::{
+    Directory, DirectoryOrder, File, FileFormat, FileSourceFile, FileSourceString,
+    FileStoredFormat, KeyPerFile,
+};
+pub use crate::format::{Format, FormatWriter};
+pub use crate::layer::{Contribution, Explanation, Layer};
 pub use crate::map::Map;
+pub use crate::merge::{ArrayMerge, DELETE};
 #[cfg(feature = "async")]
 pub use crate::source::AsyncSource;
 pub use crate::source::Source;
-pub use crate::value::{Value, ValueKind};
+pub use crate::transform::Transform;
+pub use crate::value::{Location, Value, ValueKind};
+#[cfg(feature = "watch")]
+pub use crate::watch::{Change, ConfigReader, ReloadableConfig};
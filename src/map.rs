@@ -2,3 +2,11 @@
 pub type Map<K, V> = std::collections::HashMap<K, V>;
 #[cfg(feature = "preserve_order")]
 pub type Map<K, V> = indexmap::IndexMap<K, V>;
+
+/// Removes `key` from `map`, keeping the order of the other keys.
+pub(crate) fn remove<V>(map: &mut Map<String, V>, key: &str) -> Option<V> {
+    #[cfg(feature = "preserve_order")]
+    return map.shift_remove(key);
+    #[cfg(not(feature = "preserve_order"))]
+    return map.remove(key);
+}
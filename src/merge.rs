@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+use std::mem;
+
+use crate::map::{self, Map};
+use crate::value::{Value, ValueKind};
+
+/// A string value deleting the key it is set at, with everything under it, from the layers
+/// before.
+///
+/// Sources may set it to drop keys that earlier sources set, for instance an environment file
+/// removing a section of the base file. In YAML, the `!delete` tag deletes a key as well.
+///
+/// ```toml
+/// [server]
+/// tls = "!delete"
+/// ```
+///
+/// See also [`ConfigBuilder::unset`](crate::ConfigBuilder::unset).
+pub const DELETE: &str = "!delete";
+
+/// Whether `value` is [`DELETE`].
+pub(crate) fn is_delete(value: &Value) -> bool {
+    matches!(value.kind, ValueKind::String(ref s) if s == DELETE)
+}
+
+/// How an array of a source combines with the array set at the same key before it.
+///
+/// Set for every array with [`ConfigBuilder::array_merge`](crate::ConfigBuilder::array_merge),
+/// or for the array at a key with [`ConfigBuilder::array_merge_at`](crate::ConfigBuilder::array_merge_at).
+/// Tables are always merged key by key, whatever the strategy.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ArrayMerge {
+    /// The array of the source replaces the previous one.
+    #[default]
+    Replace,
+
+    /// The items of the source come after the previous ones.
+    Append,
+
+    /// The items of the source come before the previous ones.
+    Prepend,
+
+    /// Each item of the source merges into the previous item at the same index, extra items are
+    /// appended.
+    ByIndex,
+
+    /// Each table of the source merges into the previous table with the same value for the
+    /// given field, for instance `name`, other items are appended.
+    ByKey(String),
+}
+
+/// The strategies of a builder, by key.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ArrayMerges {
+    pub(crate) default: ArrayMerge,
+    pub(crate) keys: HashMap<String, ArrayMerge>,
+}
+
+impl ArrayMerges {
+    fn get(&self, key: &str) -> &ArrayMerge {
+        self.keys.get(key).unwrap_or(&self.default)
+    }
+}
+
+/// Merges `incoming`, at `key`, into `target`: tables key by key and arrays as set in `arrays`,
+/// other values replace the previous ones. Keys set to [`DELETE`] are removed.
+pub(crate) fn merge(key: &str, target: &mut Value, mut incoming: Value, arrays: &ArrayMerges) {
+    match incoming.kind {
+        ValueKind::Table(ref mut incoming) => {
+            if !matches!(target.kind, ValueKind::Table(_)) {
+                *target = Map::<String, Value>::new().into();
+            }
+            let ValueKind::Table(ref mut table) = target.kind else {
+                unreachable!()
+            };
+
+            for (child, value) in mem::take(incoming) {
+                let path = if key.is_empty() {
+                    child.clone()
+                } else {
+                    format!("{key}.{child}")
+                };
+
+                if is_delete(&value) {
+                    map::remove(table, &child);
+                } else if let Some(existing) = table.get_mut(&child) {
+                    merge(&path, existing, value, arrays);
+                } else if matches!(value.kind, ValueKind::Table(_)) {
+                    // Tables are rebuilt key by key, like when setting a path
+                    let existing = table
+                        .entry(child)
+                        .or_insert_with(|| Map::<String, Value>::new().into());
+                    merge(&path, existing, value, arrays);
+                } else {
+                    table.insert(child, value);
+                }
+            }
+        }
+
+        ValueKind::Array(ref mut incoming_items)
+            if matches!(target.kind, ValueKind::Array(_))
+                && *arrays.get(key) != ArrayMerge::Replace =>
+        {
+            if let ValueKind::Array(ref mut items) = target.kind {
+                merge_arrays(key, items, mem::take(incoming_items), arrays);
+            }
+        }
+
+        // Including arrays replacing the previous ones
+        _ => *target = incoming,
+    }
+}
+
+fn merge_arrays(key: &str, items: &mut Vec<Value>, incoming: Vec<Value>, arrays: &ArrayMerges) {
+    match *arrays.get(key) {
+        ArrayMerge::Replace => *items = incoming,
+        ArrayMerge::Append => items.extend(incoming),
+        ArrayMerge::Prepend => {
+            items.splice(0..0, incoming);
+        }
+        ArrayMerge::ByIndex => {
+            for (index, item) in incoming.into_iter().enumerate() {
+                match items.get_mut(index) {
+                    Some(existing) => merge(&format!("{key}[{index}]"), existing, item, arrays),
+                    None => items.push(item),
+                }
+            }
+        }
+        ArrayMerge::ByKey(ref field) => {
+            for item in incoming {
+                let position = field_of(&item, field).and_then(|value| {
+                    items
+                        .iter()
+                        .position(|existing| field_of(existing, field) == Some(value))
+                });
+
+                match position {
+                    Some(index) => {
+                        merge(&format!("{key}[{index}]"), &mut items[index], item, arrays);
+                    }
+                    None => items.push(item),
+                }
+            }
+        }
+    }
+}
+
+/// The value of `field` in `item`, if it is a table holding it.
+fn field_of<'a>(item: &'a Value, field: &str) -> Option<&'a ValueKind> {
+    match item.kind {
+        ValueKind::Table(ref table) => table.get(field).map(|value| &value.kind),
+        _ => None,
+    }
+}
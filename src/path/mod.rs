@@ -1,7 +1,7 @@
 use std::str::FromStr;
 
 use crate::error::{ConfigError, Result};
-use crate::map::Map;
+use crate::map::{self, Map};
 use crate::value::{Value, ValueKind};
 
 mod parser;
@@ -23,6 +23,16 @@ impl FromStr for Expression {
     }
 }
 
+impl std::fmt::Display for Expression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Identifier(id) => write!(f, "{id}"),
+            Self::Child(expr, key) => write!(f, "{expr}.{key}"),
+            Self::Subscript(expr, index) => write!(f, "{expr}[{index}]"),
+        }
+    }
+}
+
 #[derive(Debug)]
 struct ParseError(String);
 
@@ -48,6 +58,17 @@ fn sindex_to_uindex(index: isize, len: usize) -> usize {
     }
 }
 
+/// The position of `index`, counted from the end if negative, in an array of `len` items.
+fn checked_index(index: isize, len: usize) -> Option<usize> {
+    let index = if index >= 0 {
+        index as usize
+    } else {
+        len.checked_sub(index.unsigned_abs())?
+    };
+
+    (index < len).then_some(index)
+}
+
 impl Expression {
     pub(crate) fn get(self, root: &Value) -> Option<&Value> {
         match self {
@@ -158,6 +179,61 @@ impl Expression {
         }
     }
 
+    pub(crate) fn get_mut<'a>(&self, root: &'a mut Value) -> Option<&'a mut Value> {
+        match *self {
+            Self::Identifier(ref id) => match root.kind {
+                ValueKind::Table(ref mut map) => map.get_mut(id),
+                _ => None,
+            },
+
+            Self::Child(ref expr, ref key) => match expr.get_mut(root)?.kind {
+                ValueKind::Table(ref mut map) => map.get_mut(key),
+                _ => None,
+            },
+
+            Self::Subscript(ref expr, index) => match expr.get_mut(root)?.kind {
+                ValueKind::Array(ref mut array) => {
+                    let index = checked_index(index, array.len())?;
+                    array.get_mut(index)
+                }
+                _ => None,
+            },
+        }
+    }
+
+    /// Removes the value at this path, if any.
+    pub(crate) fn remove(&self, root: &mut Value) {
+        match *self {
+            Self::Identifier(ref id) => {
+                if let ValueKind::Table(ref mut table) = root.kind {
+                    map::remove(table, id);
+                }
+            }
+
+            Self::Child(ref expr, ref key) => {
+                if let Some(Value {
+                    kind: ValueKind::Table(ref mut table),
+                    ..
+                }) = expr.get_mut(root)
+                {
+                    map::remove(table, key);
+                }
+            }
+
+            Self::Subscript(ref expr, index) => {
+                if let Some(Value {
+                    kind: ValueKind::Array(ref mut array),
+                    ..
+                }) = expr.get_mut(root)
+                {
+                    if let Some(index) = checked_index(index, array.len()) {
+                        array.remove(index);
+                    }
+                }
+            }
+        }
+    }
+
     pub(crate) fn set(&self, root: &mut Value, value: Value) {
         match *self {
             Self::Identifier(ref id) => {
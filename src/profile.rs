@@ -0,0 +1,81 @@
+use std::env;
+
+use crate::map;
+use crate::merge::{self, ArrayMerges};
+use crate::value::{Value, ValueKind};
+
+/// Top-level table of a source holding the values of every profile.
+const DEFAULT: &str = "default";
+
+/// Top-level table of a source holding a table of values per profile.
+const PROFILE: &str = "profile";
+
+/// How a builder selects the profile, see [`ConfigBuilder::profile`](crate::ConfigBuilder::profile).
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Profiles {
+    pub(crate) enabled: bool,
+    pub(crate) name: Option<String>,
+    pub(crate) env: Option<String>,
+}
+
+impl Profiles {
+    /// The profile to apply to the sources, if profiles are enabled.
+    pub(crate) fn select(&self) -> Option<Profile> {
+        if !self.enabled {
+            return None;
+        }
+
+        let from_env = self
+            .env
+            .as_ref()
+            .and_then(|var| env::var(var).ok())
+            .filter(|name| !name.is_empty());
+
+        Some(Profile {
+            name: from_env.or_else(|| self.name.clone()),
+        })
+    }
+}
+
+/// The profile selected when building, `None` to only use the default sections.
+#[derive(Clone, Debug)]
+pub(crate) struct Profile {
+    name: Option<String>,
+}
+
+impl Profile {
+    /// Merges the `[default]` section of `tree`, then its section of the profile, over the rest
+    /// of it.
+    pub(crate) fn apply(&self, mut tree: Value, arrays: &ArrayMerges) -> Value {
+        let ValueKind::Table(ref mut table) = tree.kind else {
+            return tree;
+        };
+
+        // Keys named like the sections but holding something else are left alone
+        let default = take_table(table, DEFAULT);
+        let profiles = take_table(table, PROFILE);
+
+        if let Some(default) = default {
+            merge::merge("", &mut tree, default, arrays);
+        }
+        let selected = match (profiles, &self.name) {
+            (Some(profiles), Some(name)) => profiles
+                .into_table()
+                .ok()
+                .and_then(|mut profiles| map::remove(&mut profiles, name)),
+            _ => None,
+        };
+        if let Some(selected) = selected {
+            merge::merge("", &mut tree, selected, arrays);
+        }
+
+        tree
+    }
+}
+
+fn take_table(table: &mut map::Map<String, Value>, key: &str) -> Option<Value> {
+    match table.get(key) {
+        Some(value) if matches!(value.kind, ValueKind::Table(_)) => map::remove(table, key),
+        _ => None,
+    }
+}
@@ -1,4 +1,5 @@
 use std::fmt::Debug;
+use std::path::PathBuf;
 use std::str::FromStr;
 
 #[cfg(feature = "async")]
@@ -13,6 +14,14 @@ use crate::value::{Value, ValueKind};
 pub trait Source: Debug {
     fn clone_into_box(&self) -> Box<dyn Source + Send + Sync>;
 
+    /// Describes the source, such as the path of a file, to tell it apart from the other layers of
+    /// a configuration in [`Config::explain`](crate::Config::explain).
+    ///
+    /// Defaults to the `Debug` output of the source.
+    fn describe(&self) -> String {
+        format!("{self:?}")
+    }
+
     /// Collect all configuration properties available from this source and return
     /// a Map.
     fn collect(&self) -> Result<Map<String, Value>>;
@@ -25,9 +34,31 @@ pub trait Source: Debug {
 
         Ok(())
     }
+
+    /// Collects the properties of this source as layers, each merged on top of the previous ones
+    /// the way sources are, so that arrays and [`DELETE`](crate::DELETE) combine across them.
+    ///
+    /// A [`File`](crate::File) with an include key returns the files it includes, then its own
+    /// properties. Defaults to the properties of [`collect_to`](Self::collect_to) as one layer.
+    fn collect_layers(&self) -> Result<Vec<Value>> {
+        let mut tree: Value = Map::<String, Value>::new().into();
+        self.collect_to(&mut tree)?;
+
+        Ok(vec![tree])
+    }
+
+    /// Lists the files and directories the properties of this source are read from, for a
+    /// `ReloadableConfig` to rebuild the configuration when they change.
+    ///
+    /// A directory stands for the entries directly in it. Files may not exist yet, such as an
+    /// optional file, and may be named without the extension of their format. Relative paths are
+    /// relative to the current directory. Defaults to none, for sources not read from files.
+    fn watch_paths(&self) -> Vec<PathBuf> {
+        Vec::new()
+    }
 }
 
-fn set_value(cache: &mut Value, key: &str, value: &Value) {
+pub(crate) fn set_value(cache: &mut Value, key: &str, value: &Value) {
     match path::Expression::from_str(key) {
         // Set using the path
         Ok(expr) => expr.set(cache, value.clone()),
@@ -37,6 +68,72 @@ fn set_value(cache: &mut Value, key: &str, value: &Value) {
     }
 }
 
+/// A source whose properties are nested under a key, see
+/// [`ConfigBuilder::add_source_at`](crate::ConfigBuilder::add_source_at).
+#[derive(Clone, Debug)]
+pub(crate) struct Mounted<S> {
+    pub(crate) prefix: path::Expression,
+    pub(crate) source: S,
+}
+
+impl<S> Mounted<S> {
+    /// Nests the properties collected in `tree` under the prefix.
+    fn mount(&self, tree: Value) -> Map<String, Value> {
+        let mut root: Value = Map::<String, Value>::new().into();
+        self.prefix.set(&mut root, tree);
+
+        match root.kind {
+            ValueKind::Table(table) => table,
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl Source for Mounted<Box<dyn Source + Send + Sync>> {
+    fn clone_into_box(&self) -> Box<dyn Source + Send + Sync> {
+        Box::new((*self).clone())
+    }
+
+    fn describe(&self) -> String {
+        format!("{} at \"{}\"", self.source.describe(), self.prefix)
+    }
+
+    fn collect(&self) -> Result<Map<String, Value>> {
+        let mut tree: Value = Map::<String, Value>::new().into();
+        self.source.collect_to(&mut tree)?;
+
+        Ok(self.mount(tree))
+    }
+
+    fn collect_layers(&self) -> Result<Vec<Value>> {
+        Ok(self
+            .source
+            .collect_layers()?
+            .into_iter()
+            .map(|tree| self.mount(tree).into())
+            .collect())
+    }
+
+    fn watch_paths(&self) -> Vec<PathBuf> {
+        self.source.watch_paths()
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait]
+impl AsyncSource for Mounted<Box<dyn AsyncSource + Send + Sync>> {
+    fn describe(&self) -> String {
+        format!("{} at \"{}\"", self.source.describe(), self.prefix)
+    }
+
+    async fn collect(&self) -> Result<Map<String, Value>> {
+        let mut tree: Value = Map::<String, Value>::new().into();
+        self.source.collect_to(&mut tree).await?;
+
+        Ok(self.mount(tree))
+    }
+}
+
 /// Describes a generic _source_ of configuration properties capable of using an async runtime.
 ///
 /// At the moment this library does not implement it, although it allows using its implementations
@@ -56,6 +153,11 @@ fn set_value(cache: &mut Value, key: &str, value: &Value) {
 pub trait AsyncSource: Debug + Sync {
     // Sync is supertrait due to https://docs.rs/async-trait/0.1.50/async_trait/index.html#dyn-traits
 
+    /// Describes the source, see [`Source::describe`].
+    fn describe(&self) -> String {
+        format!("{self:?}")
+    }
+
     /// Collects all configuration properties available from this source and return
     /// a Map as an async operations.
     async fn collect(&self) -> Result<Map<String, Value>>;
@@ -89,6 +191,10 @@ impl Source for Vec<Box<dyn Source + Send + Sync>> {
         Box::new((*self).clone())
     }
 
+    fn describe(&self) -> String {
+        describe_all(self.iter().map(|source| source.describe()))
+    }
+
     fn collect(&self) -> Result<Map<String, Value>> {
         let mut cache: Value = Map::<String, Value>::new().into();
 
@@ -102,6 +208,12 @@ impl Source for Vec<Box<dyn Source + Send + Sync>> {
             unreachable!();
         }
     }
+
+    fn watch_paths(&self) -> Vec<PathBuf> {
+        self.iter()
+            .flat_map(|source| source.watch_paths())
+            .collect()
+    }
 }
 
 impl Source for [Box<dyn Source + Send + Sync>] {
@@ -109,6 +221,10 @@ impl Source for [Box<dyn Source + Send + Sync>] {
         Box::new(self.to_owned())
     }
 
+    fn describe(&self) -> String {
+        describe_all(self.iter().map(|source| source.describe()))
+    }
+
     fn collect(&self) -> Result<Map<String, Value>> {
         let mut cache: Value = Map::<String, Value>::new().into();
 
@@ -122,6 +238,12 @@ impl Source for [Box<dyn Source + Send + Sync>] {
             unreachable!();
         }
     }
+
+    fn watch_paths(&self) -> Vec<PathBuf> {
+        self.iter()
+            .flat_map(|source| source.watch_paths())
+            .collect()
+    }
 }
 
 impl<T> Source for Vec<T>
@@ -132,6 +254,10 @@ where
         Box::new((*self).clone())
     }
 
+    fn describe(&self) -> String {
+        describe_all(self.iter().map(|source| source.describe()))
+    }
+
     fn collect(&self) -> Result<Map<String, Value>> {
         let mut cache: Value = Map::<String, Value>::new().into();
 
@@ -145,4 +271,15 @@ where
             unreachable!();
         }
     }
+
+    fn watch_paths(&self) -> Vec<PathBuf> {
+        self.iter()
+            .flat_map(|source| source.watch_paths())
+            .collect()
+    }
+}
+
+/// Describes a list of sources by the descriptions of each of them.
+fn describe_all(descriptions: impl Iterator<Item = String>) -> String {
+    format!("[{}]", descriptions.collect::<Vec<_>>().join(", "))
 }
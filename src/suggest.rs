@@ -0,0 +1,106 @@
+use crate::value::{Value, ValueKind};
+
+/// How many suggestions an error lists at most.
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Picks the candidates close enough to `target` to be what was meant, closest first.
+///
+/// A candidate qualifies if it is at most a third of the length of `target` edits away from
+/// it (and at least one), ignoring case, where swapping two adjacent characters is one edit.
+pub(crate) fn suggest<'a>(
+    target: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Vec<String> {
+    let target = target.to_lowercase();
+    let threshold = (target.chars().count() / 3).max(1);
+
+    let mut close: Vec<(usize, &str)> = candidates
+        .into_iter()
+        .map(|candidate| (distance(&target, &candidate.to_lowercase()), candidate))
+        .filter(|&(distance, _)| distance <= threshold)
+        .collect();
+    close.sort_unstable();
+    close.dedup();
+
+    close
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, candidate)| candidate.to_owned())
+        .collect()
+}
+
+/// Lists the paths of every key of the tables in `root`, as accepted by [`Config::get`](crate::Config::get).
+pub(crate) fn key_paths(root: &Value) -> Vec<String> {
+    fn walk(value: &Value, prefix: &str, paths: &mut Vec<String>) {
+        if let ValueKind::Table(ref table) = value.kind {
+            for (key, value) in table {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                walk(value, &path, paths);
+                paths.push(path);
+            }
+        }
+    }
+
+    let mut paths = Vec::new();
+    walk(root, "", &mut paths);
+    paths
+}
+
+/// Edit distance between `a` and `b`, counting the transposition of two adjacent characters as
+/// one edit (the optimal string alignment distance).
+fn distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    // Three rows of the matrix: two rows back, the previous one and the current one
+    let mut before: Vec<usize> = vec![0; b.len() + 1];
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current: Vec<usize> = vec![0; b.len() + 1];
+
+    for i in 1..=a.len() {
+        current[0] = i;
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            current[j] = (previous[j] + 1)
+                .min(current[j - 1] + 1)
+                .min(previous[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                current[j] = current[j].min(before[j - 2] + 1);
+            }
+        }
+        std::mem::swap(&mut before, &mut previous);
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_distance() {
+        assert_eq!(distance("port", "port"), 0);
+        assert_eq!(distance("port", "prot"), 1);
+        assert_eq!(distance("databse", "database"), 1);
+        assert_eq!(distance("host", "port"), 2);
+        assert_eq!(distance("", "url"), 3);
+    }
+
+    #[test]
+    fn test_suggest() {
+        let candidates = ["database.url", "database.uri", "database.user", "debug"];
+
+        assert_eq!(
+            suggest("databse.url", candidates),
+            vec!["database.url", "database.uri"]
+        );
+        assert_eq!(suggest("DEBUG", candidates), vec!["debug"]);
+        assert!(suggest("name", candidates).is_empty());
+    }
+}
@@ -0,0 +1,449 @@
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+
+#[cfg(feature = "async")]
+use async_trait::async_trait;
+#[cfg(feature = "convert-case")]
+use convert_case::{Case, Casing};
+
+use crate::error::{ConfigError, Result};
+use crate::map::Map;
+use crate::path::Expression;
+#[cfg(feature = "async")]
+use crate::source::AsyncSource;
+use crate::source::Source;
+use crate::value::{Value, ValueKind};
+
+/// A [`Source`] or [`AsyncSource`] whose keys and values are transformed before they are merged.
+///
+/// The transformations apply in the order they are added, to the properties the wrapped source
+/// collected, as a tree: the keys of [`Environment`](crate::Environment) are already split at the
+/// separator, for instance.
+///
+/// ```rust
+/// # use config::*;
+/// # fn main() -> Result<(), ConfigError> {
+/// # #[cfg(feature = "json")]
+/// # {
+/// let source = File::from_str(
+///     r#"{"DB": {"Host": "localhost", "Password": "hunter2"}, "Debug": true}"#,
+///     FileFormat::Json,
+/// );
+/// let config = Config::builder()
+///     .add_source(
+///         Transform::new(source)
+///             .lowercase_keys()
+///             .keep("db.*")?
+///             .exclude("db.password")?
+///             .rename("db", "database")?,
+///     )
+///     .build()?;
+///
+/// assert_eq!(config.get_string("database.host")?, "localhost");
+/// assert!(config.get_string("database.password").is_err());
+/// assert!(config.get_bool("debug").is_err());
+/// # }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+#[must_use]
+pub struct Transform<S> {
+    source: S,
+    steps: Vec<Step>,
+}
+
+/// A closure mapping a value, given its path.
+type ValueMapper = dyn Fn(&str, Value) -> Value + Send + Sync;
+
+#[derive(Clone)]
+enum Step {
+    Keep(Vec<KeyPattern>),
+    Exclude(KeyPattern),
+    Rename(Expression, Expression),
+    MapValues(Arc<ValueMapper>),
+    Lowercase,
+    #[cfg(feature = "convert-case")]
+    ConvertCase(Case),
+}
+
+impl<S> Transform<S> {
+    /// Wraps `source`, leaving its properties as they are until transformations are added.
+    pub fn new(source: S) -> Self {
+        Self {
+            source,
+            steps: Vec::new(),
+        }
+    }
+
+    /// Keeps only the keys matching `pattern`, with everything under them.
+    ///
+    /// Patterns are matched against the path of the keys, segment by segment: `*` and `?`
+    /// match within a segment and `**` matches any number of segments, so `db.*` matches
+    /// `db.host` but not `db` itself, while `**.password` matches `password` at any depth.
+    /// Consecutive calls keep the keys matching any of the patterns.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `pattern` is not a valid glob pattern.
+    pub fn keep(mut self, pattern: &str) -> Result<Self> {
+        let pattern = pattern.parse()?;
+        match self.steps.last_mut() {
+            Some(Step::Keep(patterns)) => patterns.push(pattern),
+            _ => self.steps.push(Step::Keep(vec![pattern])),
+        }
+        Ok(self)
+    }
+
+    /// Drops the keys matching `pattern`, with everything under them, see
+    /// [`keep`](Self::keep) for the syntax of patterns.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `pattern` is not a valid glob pattern.
+    pub fn exclude(mut self, pattern: &str) -> Result<Self> {
+        self.steps.push(Step::Exclude(pattern.parse()?));
+        Ok(self)
+    }
+
+    /// Moves the value at `from`, with everything under it, to `to`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `Expression::from_str` fails for `from` or `to`.
+    pub fn rename(mut self, from: &str, to: &str) -> Result<Self> {
+        self.steps.push(Step::Rename(
+            Expression::from_str(from)?,
+            Expression::from_str(to)?,
+        ));
+        Ok(self)
+    }
+
+    /// Replaces every value other than tables and arrays by the result of `f`, given its path
+    /// (such as `servers[0].port`) and the value.
+    pub fn map_values<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&str, Value) -> Value + Send + Sync + 'static,
+    {
+        self.steps.push(Step::MapValues(Arc::new(f)));
+        self
+    }
+
+    /// Lowercases every key.
+    pub fn lowercase_keys(mut self) -> Self {
+        self.steps.push(Step::Lowercase);
+        self
+    }
+
+    /// Converts every key to the case `tt`, as [`Environment::convert_case`](crate::Environment::convert_case) does.
+    #[cfg(feature = "convert-case")]
+    pub fn convert_case(mut self, tt: Case) -> Self {
+        self.steps.push(Step::ConvertCase(tt));
+        self
+    }
+
+    fn apply(&self, mut tree: Value) -> Map<String, Value> {
+        for step in &self.steps {
+            match *step {
+                Step::Keep(ref patterns) => {
+                    if let ValueKind::Table(ref mut table) = tree.kind {
+                        keep(table, &mut Vec::new(), patterns);
+                    }
+                }
+                Step::Exclude(ref pattern) => {
+                    if let ValueKind::Table(ref mut table) = tree.kind {
+                        exclude(table, &mut Vec::new(), pattern);
+                    }
+                }
+                Step::Rename(ref from, ref to) => {
+                    if let Some(value) = from.clone().get(&tree).cloned() {
+                        from.remove(&mut tree);
+                        to.set(&mut tree, value);
+                    }
+                }
+                Step::MapValues(ref f) => map_values("", &mut tree, f.as_ref()),
+                Step::Lowercase => map_keys(&mut tree, &|key| key.to_lowercase()),
+                #[cfg(feature = "convert-case")]
+                Step::ConvertCase(tt) => map_keys(&mut tree, &|key| key.to_case(tt)),
+            }
+        }
+
+        match tree.kind {
+            ValueKind::Table(table) => table,
+            _ => Map::new(),
+        }
+    }
+}
+
+impl<S: fmt::Debug> fmt::Debug for Transform<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Transform")
+            .field("source", &self.source)
+            .field("steps", &self.steps.len())
+            .finish()
+    }
+}
+
+impl<S> Source for Transform<S>
+where
+    S: Source + Clone + Send + Sync + 'static,
+{
+    fn clone_into_box(&self) -> Box<dyn Source + Send + Sync> {
+        Box::new((*self).clone())
+    }
+
+    fn describe(&self) -> String {
+        self.source.describe()
+    }
+
+    fn collect(&self) -> Result<Map<String, Value>> {
+        let mut tree: Value = Map::<String, Value>::new().into();
+        self.source.collect_to(&mut tree)?;
+
+        Ok(self.apply(tree))
+    }
+
+    fn collect_layers(&self) -> Result<Vec<Value>> {
+        Ok(self
+            .source
+            .collect_layers()?
+            .into_iter()
+            .map(|tree| self.apply(tree).into())
+            .collect())
+    }
+
+    fn watch_paths(&self) -> Vec<PathBuf> {
+        self.source.watch_paths()
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait]
+impl<S> AsyncSource for Transform<S>
+where
+    S: AsyncSource + Send + Sync,
+{
+    fn describe(&self) -> String {
+        self.source.describe()
+    }
+
+    async fn collect(&self) -> Result<Map<String, Value>> {
+        let mut tree: Value = Map::<String, Value>::new().into();
+        self.source.collect_to(&mut tree).await?;
+
+        Ok(self.apply(tree))
+    }
+}
+
+/// A glob pattern over the path of a key.
+#[derive(Clone, Debug)]
+struct KeyPattern(Vec<PatternSegment>);
+
+#[derive(Clone, Debug)]
+enum PatternSegment {
+    /// `**`, any number of segments.
+    Any,
+    Glob(Vec<Token>),
+}
+
+/// A part of a segment of a [`KeyPattern`].
+#[derive(Clone, Debug)]
+enum Token {
+    /// `*`, any number of characters.
+    Star,
+    /// `?`, one character.
+    Question,
+    /// `[a-z]` or `[!a-z]`, one character in, or out of, the ranges.
+    Class(bool, Vec<(char, char)>),
+    Char(char),
+}
+
+impl FromStr for KeyPattern {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        s.split('.')
+            .map(|segment| match segment {
+                "**" => Ok(PatternSegment::Any),
+                _ => parse_segment(segment)
+                    .map(PatternSegment::Glob)
+                    .ok_or_else(|| {
+                        ConfigError::Message(format!("invalid key pattern `{s}`: unclosed `[`"))
+                    }),
+            })
+            .collect::<Result<_>>()
+            .map(KeyPattern)
+    }
+}
+
+/// Parses the wildcards of a segment, or returns `None` if a `[` is not closed.
+fn parse_segment(segment: &str) -> Option<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = segment.chars().peekable();
+    while let Some(c) = chars.next() {
+        let token = match c {
+            '*' => Token::Star,
+            '?' => Token::Question,
+            '[' => {
+                let negated = chars.next_if_eq(&'!').is_some();
+                let mut ranges = Vec::new();
+                // A `]` right after the bracket is one of the characters
+                let mut first = true;
+                loop {
+                    let start = chars.next()?;
+                    if start == ']' && !first {
+                        break;
+                    }
+                    first = false;
+                    let end = match chars.next_if_eq(&'-') {
+                        Some(_) if chars.peek() != Some(&']') => chars.next()?,
+                        Some(_) => {
+                            ranges.push(('-', '-'));
+                            start
+                        }
+                        None => start,
+                    };
+                    ranges.push((start, end));
+                }
+                Token::Class(negated, ranges)
+            }
+            c => Token::Char(c),
+        };
+        tokens.push(token);
+    }
+
+    Some(tokens)
+}
+
+/// Whether `key` matches all of `tokens`.
+fn matches_segment(tokens: &[Token], key: &str) -> bool {
+    let Some((token, rest)) = tokens.split_first() else {
+        return key.is_empty();
+    };
+    if let Token::Star = token {
+        return key
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain(Some(key.len()))
+            .any(|i| matches_segment(rest, &key[i..]));
+    }
+
+    let mut chars = key.chars();
+    let Some(c) = chars.next() else {
+        return false;
+    };
+    let matched = match *token {
+        Token::Star | Token::Question => true,
+        Token::Class(negated, ref ranges) => {
+            ranges
+                .iter()
+                .any(|&(start, end)| (start..=end).contains(&c))
+                != negated
+        }
+        Token::Char(expected) => c == expected,
+    };
+
+    matched && matches_segment(rest, chars.as_str())
+}
+
+impl KeyPattern {
+    /// Whether the pattern matches `path`, or if `partial`, a path starting with `path`.
+    fn matches(&self, path: &[String], partial: bool) -> bool {
+        fn matches(pattern: &[PatternSegment], path: &[String], partial: bool) -> bool {
+            match (pattern.split_first(), path.split_first()) {
+                (None, None) => true,
+                (None, Some(_)) => false,
+                (Some((PatternSegment::Any, rest)), _) => {
+                    matches(rest, path, partial)
+                        || (!path.is_empty() && matches(pattern, &path[1..], partial))
+                }
+                (Some(_), None) => partial,
+                (Some((PatternSegment::Glob(tokens), rest)), Some((key, tail))) => {
+                    matches_segment(tokens, key) && matches(rest, tail, partial)
+                }
+            }
+        }
+
+        matches(&self.0, path, partial)
+    }
+}
+
+fn keep(table: &mut Map<String, Value>, path: &mut Vec<String>, patterns: &[KeyPattern]) {
+    table.retain(|key, value| {
+        path.push(key.clone());
+        let kept = if patterns.iter().any(|p| p.matches(path, false)) {
+            true
+        } else if patterns.iter().any(|p| p.matches(path, true)) {
+            match value.kind {
+                ValueKind::Table(ref mut table) => {
+                    keep(table, path, patterns);
+                    !table.is_empty()
+                }
+                _ => false,
+            }
+        } else {
+            false
+        };
+        path.pop();
+
+        kept
+    });
+}
+
+fn exclude(table: &mut Map<String, Value>, path: &mut Vec<String>, pattern: &KeyPattern) {
+    table.retain(|key, value| {
+        path.push(key.clone());
+        let kept = !pattern.matches(path, false);
+        if kept {
+            if let ValueKind::Table(ref mut table) = value.kind {
+                exclude(table, path, pattern);
+            }
+        }
+        path.pop();
+
+        kept
+    });
+}
+
+fn map_values(key: &str, value: &mut Value, f: &ValueMapper) {
+    match value.kind {
+        ValueKind::Table(ref mut table) => {
+            for (child, value) in table.iter_mut() {
+                let path = if key.is_empty() {
+                    child.clone()
+                } else {
+                    format!("{key}.{child}")
+                };
+                map_values(&path, value, f);
+            }
+        }
+        ValueKind::Array(ref mut array) => {
+            for (index, value) in array.iter_mut().enumerate() {
+                map_values(&format!("{key}[{index}]"), value, f);
+            }
+        }
+        _ => *value = f(key, std::mem::take(value)),
+    }
+}
+
+fn map_keys(value: &mut Value, f: &dyn Fn(&str) -> String) {
+    match value.kind {
+        ValueKind::Table(ref mut table) => {
+            *table = std::mem::take(table)
+                .into_iter()
+                .map(|(key, mut value)| {
+                    map_keys(&mut value, f);
+                    (f(&key), value)
+                })
+                .collect();
+        }
+        ValueKind::Array(ref mut array) => {
+            for value in array {
+                map_keys(value, f);
+            }
+        }
+        _ => {}
+    }
+}
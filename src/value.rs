@@ -1,8 +1,12 @@
 use std::convert::TryInto;
 use std::fmt;
 use std::fmt::Display;
+use std::num::NonZeroU32;
+use std::ops::Range;
+use std::sync::Arc;
 
 use serde::de::{Deserialize, Deserializer, Visitor};
+use serde::ser::{Serialize, SerializeMap, SerializeSeq, Serializer};
 
 use crate::error::{ConfigError, Result, Unexpected};
 use crate::map::Map;
@@ -181,8 +185,144 @@ impl Display for ValueKind {
     }
 }
 
+/// Position of a [`Value`] in the text it was parsed from.
+#[derive(Clone)]
+pub struct Location {
+    // Kept narrow, with the rest behind a pointer, so errors holding a location stay small
+    line: NonZeroU32,
+    column: u32,
+    extent: Option<Arc<Extent>>,
+}
+
+/// What is known of a [`Location`] besides its line and column.
+#[derive(Clone, Default)]
+struct Extent {
+    span: Option<Range<usize>>,
+    // Shared by the values of a file, so diagnostics can quote it without reading it again
+    #[cfg(feature = "diagnostics")]
+    source: Option<Arc<String>>,
+}
+
+impl Location {
+    #[cfg(any(
+        feature = "toml",
+        feature = "json",
+        feature = "yaml",
+        feature = "ini",
+        feature = "ron",
+        feature = "json5",
+        feature = "dotenv",
+        feature = "properties",
+        feature = "xml",
+        feature = "hcl",
+        feature = "kdl"
+    ))]
+    pub(crate) fn new(line: usize, column: usize) -> Self {
+        Self {
+            line: NonZeroU32::new(narrow(line)).unwrap_or(NonZeroU32::MIN),
+            column: narrow(column),
+            extent: None,
+        }
+    }
+
+    #[cfg(any(
+        feature = "toml",
+        feature = "json",
+        feature = "json5",
+        feature = "xml",
+        feature = "kdl"
+    ))]
+    #[must_use]
+    pub(crate) fn with_span(mut self, span: Range<usize>) -> Self {
+        self.extent_mut().span = Some(span);
+        self
+    }
+
+    /// The line of the value, starting at 1.
+    pub fn line(&self) -> usize {
+        self.line.get() as usize
+    }
+
+    /// The column of the value in characters, starting at 1.
+    pub fn column(&self) -> usize {
+        self.column as usize
+    }
+
+    /// The range of bytes the value spans in the text, if known.
+    pub fn span(&self) -> Option<Range<usize>> {
+        self.extent.as_ref()?.span.clone()
+    }
+
+    #[cfg(feature = "diagnostics")]
+    #[must_use]
+    pub(crate) fn with_source(mut self, source: &Arc<String>) -> Self {
+        self.extent_mut().source = Some(Arc::clone(source));
+        self
+    }
+
+    /// The text the value was parsed from, if it was captured.
+    #[cfg(feature = "diagnostics")]
+    pub(crate) fn source(&self) -> Option<&str> {
+        self.extent.as_ref()?.source.as_deref().map(String::as_str)
+    }
+
+    #[cfg(any(
+        feature = "toml",
+        feature = "json",
+        feature = "json5",
+        feature = "xml",
+        feature = "kdl",
+        feature = "diagnostics"
+    ))]
+    fn extent_mut(&mut self) -> &mut Extent {
+        Arc::make_mut(self.extent.get_or_insert_with(Default::default))
+    }
+}
+
+// The source is left out, it is the same for every location of a file
+impl fmt::Debug for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Location")
+            .field("line", &self.line)
+            .field("column", &self.column)
+            .field("span", &self.span())
+            .finish()
+    }
+}
+
+impl PartialEq for Location {
+    fn eq(&self, other: &Self) -> bool {
+        (self.line, self.column, self.span()) == (other.line, other.column, other.span())
+    }
+}
+
+impl Eq for Location {}
+
+#[cfg(any(
+    feature = "toml",
+    feature = "json",
+    feature = "yaml",
+    feature = "ini",
+    feature = "ron",
+    feature = "json5",
+    feature = "dotenv",
+    feature = "properties",
+    feature = "xml",
+    feature = "hcl",
+    feature = "kdl"
+))]
+fn narrow(n: usize) -> u32 {
+    n.try_into().unwrap_or(u32::MAX)
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
 /// A configuration value.
-#[derive(Default, Debug, Clone, PartialEq)]
+#[derive(Default, Debug, Clone)]
 pub struct Value {
     /// A description of the original location of the value.
     ///
@@ -202,10 +342,20 @@ pub struct Value {
     /// ```
     origin: Option<String>,
 
+    /// Position of the value in the text it was parsed from, if it was parsed from a file.
+    pub(crate) location: Option<Location>,
+
     /// Underlying kind of the configuration value.
     pub kind: ValueKind,
 }
 
+// The location is left out, values are equal regardless of where they were written
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        self.origin == other.origin && self.kind == other.kind
+    }
+}
+
 impl Value {
     /// Create a new value instance that will remember its source uri.
     pub fn new<V>(origin: Option<&String>, kind: V) -> Self
@@ -214,6 +364,7 @@ impl Value {
     {
         Self {
             origin: origin.cloned(),
+            location: None,
             kind: kind.into(),
         }
     }
@@ -223,6 +374,41 @@ impl Value {
         self.origin.as_ref().map(AsRef::as_ref)
     }
 
+    /// Get the line and column the value was written at, if it was parsed from a file.
+    pub fn location(&self) -> Option<Location> {
+        self.location.clone()
+    }
+
+    /// Attaches `source` to the locations of the value and of the values it holds.
+    #[cfg(feature = "diagnostics")]
+    pub(crate) fn attach_source(&mut self, source: &Arc<String>) {
+        if let Some(location) = self.location.take() {
+            self.location = Some(location.with_source(source));
+        }
+        match self.kind {
+            ValueKind::Table(ref mut table) => {
+                for value in table.values_mut() {
+                    value.attach_source(source);
+                }
+            }
+            ValueKind::Array(ref mut array) => {
+                for value in array {
+                    value.attach_source(source);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub(crate) fn invalid_type(
+        &self,
+        unexpected: Unexpected,
+        expected: &'static str,
+    ) -> ConfigError {
+        ConfigError::invalid_type(self.origin.clone(), unexpected, expected)
+            .at(self.location.clone())
+    }
+
     /// Attempt to deserialize this value into the requested type.
     pub fn try_deserialize<'de, T: Deserialize<'de>>(self) -> Result<T> {
         T::deserialize(self)
@@ -245,30 +431,14 @@ impl Value {
                     "0" | "false" | "off" | "no" => Ok(false),
 
                     // Unexpected string value
-                    s => Err(ConfigError::invalid_type(
-                        self.origin.clone(),
-                        Unexpected::Str(s.into()),
-                        "a boolean",
-                    )),
+                    s => Err(self.invalid_type(Unexpected::Str(s.into()), "a boolean")),
                 }
             }
 
             // Unexpected type
-            ValueKind::Nil => Err(ConfigError::invalid_type(
-                self.origin,
-                Unexpected::Unit,
-                "a boolean",
-            )),
-            ValueKind::Table(_) => Err(ConfigError::invalid_type(
-                self.origin,
-                Unexpected::Map,
-                "a boolean",
-            )),
-            ValueKind::Array(_) => Err(ConfigError::invalid_type(
-                self.origin,
-                Unexpected::Seq,
-                "a boolean",
-            )),
+            ValueKind::Nil => Err(self.invalid_type(Unexpected::Unit, "a boolean")),
+            ValueKind::Table(_) => Err(self.invalid_type(Unexpected::Map, "a boolean")),
+            ValueKind::Array(_) => Err(self.invalid_type(Unexpected::Seq, "a boolean")),
         }
     }
 
@@ -278,25 +448,13 @@ impl Value {
         match self.kind {
             ValueKind::I64(value) => Ok(value),
             ValueKind::I128(value) => value.try_into().map_err(|_| {
-                ConfigError::invalid_type(
-                    self.origin,
-                    Unexpected::I128(value),
-                    "an signed 64 bit or less integer",
-                )
+                self.invalid_type(Unexpected::I128(value), "an signed 64 bit or less integer")
             }),
             ValueKind::U64(value) => value.try_into().map_err(|_| {
-                ConfigError::invalid_type(
-                    self.origin,
-                    Unexpected::U64(value),
-                    "an signed 64 bit or less integer",
-                )
+                self.invalid_type(Unexpected::U64(value), "an signed 64 bit or less integer")
             }),
             ValueKind::U128(value) => value.try_into().map_err(|_| {
-                ConfigError::invalid_type(
-                    self.origin,
-                    Unexpected::U128(value),
-                    "an signed 64 bit or less integer",
-                )
+                self.invalid_type(Unexpected::U128(value), "an signed 64 bit or less integer")
             }),
 
             ValueKind::String(ref s) => {
@@ -306,11 +464,7 @@ impl Value {
                     _ => {
                         s.parse().map_err(|_| {
                             // Unexpected string
-                            ConfigError::invalid_type(
-                                self.origin.clone(),
-                                Unexpected::Str(s.clone()),
-                                "an integer",
-                            )
+                            self.invalid_type(Unexpected::Str(s.clone()), "an integer")
                         })
                     }
                 }
@@ -320,21 +474,9 @@ impl Value {
             ValueKind::Float(value) => Ok(value.round() as i64),
 
             // Unexpected type
-            ValueKind::Nil => Err(ConfigError::invalid_type(
-                self.origin,
-                Unexpected::Unit,
-                "an integer",
-            )),
-            ValueKind::Table(_) => Err(ConfigError::invalid_type(
-                self.origin,
-                Unexpected::Map,
-                "an integer",
-            )),
-            ValueKind::Array(_) => Err(ConfigError::invalid_type(
-                self.origin,
-                Unexpected::Seq,
-                "an integer",
-            )),
+            ValueKind::Nil => Err(self.invalid_type(Unexpected::Unit, "an integer")),
+            ValueKind::Table(_) => Err(self.invalid_type(Unexpected::Map, "an integer")),
+            ValueKind::Array(_) => Err(self.invalid_type(Unexpected::Seq, "an integer")),
         }
     }
 
@@ -345,11 +487,7 @@ impl Value {
             ValueKind::I128(value) => Ok(value),
             ValueKind::U64(value) => Ok(value.into()),
             ValueKind::U128(value) => value.try_into().map_err(|_| {
-                ConfigError::invalid_type(
-                    self.origin,
-                    Unexpected::U128(value),
-                    "an signed 128 bit integer",
-                )
+                self.invalid_type(Unexpected::U128(value), "an signed 128 bit integer")
             }),
 
             ValueKind::String(ref s) => {
@@ -359,11 +497,7 @@ impl Value {
                     _ => {
                         s.parse().map_err(|_| {
                             // Unexpected string
-                            ConfigError::invalid_type(
-                                self.origin.clone(),
-                                Unexpected::Str(s.clone()),
-                                "an integer",
-                            )
+                            self.invalid_type(Unexpected::Str(s.clone()), "an integer")
                         })
                     }
                 }
@@ -373,21 +507,9 @@ impl Value {
             ValueKind::Float(value) => Ok(value.round() as i128),
 
             // Unexpected type
-            ValueKind::Nil => Err(ConfigError::invalid_type(
-                self.origin,
-                Unexpected::Unit,
-                "an integer",
-            )),
-            ValueKind::Table(_) => Err(ConfigError::invalid_type(
-                self.origin,
-                Unexpected::Map,
-                "an integer",
-            )),
-            ValueKind::Array(_) => Err(ConfigError::invalid_type(
-                self.origin,
-                Unexpected::Seq,
-                "an integer",
-            )),
+            ValueKind::Nil => Err(self.invalid_type(Unexpected::Unit, "an integer")),
+            ValueKind::Table(_) => Err(self.invalid_type(Unexpected::Map, "an integer")),
+            ValueKind::Array(_) => Err(self.invalid_type(Unexpected::Seq, "an integer")),
         }
     }
 
@@ -397,22 +519,16 @@ impl Value {
         match self.kind {
             ValueKind::U64(value) => Ok(value),
             ValueKind::U128(value) => value.try_into().map_err(|_| {
-                ConfigError::invalid_type(
-                    self.origin,
+                self.invalid_type(
                     Unexpected::U128(value),
                     "an unsigned 64 bit or less integer",
                 )
             }),
             ValueKind::I64(value) => value.try_into().map_err(|_| {
-                ConfigError::invalid_type(
-                    self.origin,
-                    Unexpected::I64(value),
-                    "an unsigned 64 bit or less integer",
-                )
+                self.invalid_type(Unexpected::I64(value), "an unsigned 64 bit or less integer")
             }),
             ValueKind::I128(value) => value.try_into().map_err(|_| {
-                ConfigError::invalid_type(
-                    self.origin,
+                self.invalid_type(
                     Unexpected::I128(value),
                     "an unsigned 64 bit or less integer",
                 )
@@ -425,11 +541,7 @@ impl Value {
                     _ => {
                         s.parse().map_err(|_| {
                             // Unexpected string
-                            ConfigError::invalid_type(
-                                self.origin.clone(),
-                                Unexpected::Str(s.clone()),
-                                "an integer",
-                            )
+                            self.invalid_type(Unexpected::Str(s.clone()), "an integer")
                         })
                     }
                 }
@@ -439,21 +551,9 @@ impl Value {
             ValueKind::Float(value) => Ok(value.round() as u64),
 
             // Unexpected type
-            ValueKind::Nil => Err(ConfigError::invalid_type(
-                self.origin,
-                Unexpected::Unit,
-                "an integer",
-            )),
-            ValueKind::Table(_) => Err(ConfigError::invalid_type(
-                self.origin,
-                Unexpected::Map,
-                "an integer",
-            )),
-            ValueKind::Array(_) => Err(ConfigError::invalid_type(
-                self.origin,
-                Unexpected::Seq,
-                "an integer",
-            )),
+            ValueKind::Nil => Err(self.invalid_type(Unexpected::Unit, "an integer")),
+            ValueKind::Table(_) => Err(self.invalid_type(Unexpected::Map, "an integer")),
+            ValueKind::Array(_) => Err(self.invalid_type(Unexpected::Seq, "an integer")),
         }
     }
 
@@ -463,15 +563,13 @@ impl Value {
             ValueKind::U64(value) => Ok(value.into()),
             ValueKind::U128(value) => Ok(value),
             ValueKind::I64(value) => value.try_into().map_err(|_| {
-                ConfigError::invalid_type(
-                    self.origin,
+                self.invalid_type(
                     Unexpected::I64(value),
                     "an unsigned 128 bit or less integer",
                 )
             }),
             ValueKind::I128(value) => value.try_into().map_err(|_| {
-                ConfigError::invalid_type(
-                    self.origin,
+                self.invalid_type(
                     Unexpected::I128(value),
                     "an unsigned 128 bit or less integer",
                 )
@@ -484,11 +582,7 @@ impl Value {
                     _ => {
                         s.parse().map_err(|_| {
                             // Unexpected string
-                            ConfigError::invalid_type(
-                                self.origin.clone(),
-                                Unexpected::Str(s.clone()),
-                                "an integer",
-                            )
+                            self.invalid_type(Unexpected::Str(s.clone()), "an integer")
                         })
                     }
                 }
@@ -498,21 +592,9 @@ impl Value {
             ValueKind::Float(value) => Ok(value.round() as u128),
 
             // Unexpected type
-            ValueKind::Nil => Err(ConfigError::invalid_type(
-                self.origin,
-                Unexpected::Unit,
-                "an integer",
-            )),
-            ValueKind::Table(_) => Err(ConfigError::invalid_type(
-                self.origin,
-                Unexpected::Map,
-                "an integer",
-            )),
-            ValueKind::Array(_) => Err(ConfigError::invalid_type(
-                self.origin,
-                Unexpected::Seq,
-                "an integer",
-            )),
+            ValueKind::Nil => Err(self.invalid_type(Unexpected::Unit, "an integer")),
+            ValueKind::Table(_) => Err(self.invalid_type(Unexpected::Map, "an integer")),
+            ValueKind::Array(_) => Err(self.invalid_type(Unexpected::Seq, "an integer")),
         }
     }
 
@@ -529,11 +611,7 @@ impl Value {
                     _ => {
                         s.parse().map_err(|_| {
                             // Unexpected string
-                            ConfigError::invalid_type(
-                                self.origin.clone(),
-                                Unexpected::Str(s.clone()),
-                                "a floating point",
-                            )
+                            self.invalid_type(Unexpected::Str(s.clone()), "a floating point")
                         })
                     }
                 }
@@ -546,21 +624,9 @@ impl Value {
             ValueKind::Boolean(value) => Ok(if value { 1.0 } else { 0.0 }),
 
             // Unexpected type
-            ValueKind::Nil => Err(ConfigError::invalid_type(
-                self.origin,
-                Unexpected::Unit,
-                "a floating point",
-            )),
-            ValueKind::Table(_) => Err(ConfigError::invalid_type(
-                self.origin,
-                Unexpected::Map,
-                "a floating point",
-            )),
-            ValueKind::Array(_) => Err(ConfigError::invalid_type(
-                self.origin,
-                Unexpected::Seq,
-                "a floating point",
-            )),
+            ValueKind::Nil => Err(self.invalid_type(Unexpected::Unit, "a floating point")),
+            ValueKind::Table(_) => Err(self.invalid_type(Unexpected::Map, "a floating point")),
+            ValueKind::Array(_) => Err(self.invalid_type(Unexpected::Seq, "a floating point")),
         }
     }
 
@@ -578,21 +644,9 @@ impl Value {
             ValueKind::Float(value) => Ok(value.to_string()),
 
             // Cannot convert
-            ValueKind::Nil => Err(ConfigError::invalid_type(
-                self.origin,
-                Unexpected::Unit,
-                "a string",
-            )),
-            ValueKind::Table(_) => Err(ConfigError::invalid_type(
-                self.origin,
-                Unexpected::Map,
-                "a string",
-            )),
-            ValueKind::Array(_) => Err(ConfigError::invalid_type(
-                self.origin,
-                Unexpected::Seq,
-                "a string",
-            )),
+            ValueKind::Nil => Err(self.invalid_type(Unexpected::Unit, "a string")),
+            ValueKind::Table(_) => Err(self.invalid_type(Unexpected::Map, "a string")),
+            ValueKind::Array(_) => Err(self.invalid_type(Unexpected::Seq, "a string")),
         }
     }
 
@@ -603,51 +657,19 @@ impl Value {
             ValueKind::Array(value) => Ok(value),
 
             // Cannot convert
-            ValueKind::Float(value) => Err(ConfigError::invalid_type(
-                self.origin,
-                Unexpected::Float(value),
-                "an array",
-            )),
-            ValueKind::String(value) => Err(ConfigError::invalid_type(
-                self.origin,
-                Unexpected::Str(value),
-                "an array",
-            )),
-            ValueKind::I64(value) => Err(ConfigError::invalid_type(
-                self.origin,
-                Unexpected::I64(value),
-                "an array",
-            )),
-            ValueKind::I128(value) => Err(ConfigError::invalid_type(
-                self.origin,
-                Unexpected::I128(value),
-                "an array",
-            )),
-            ValueKind::U64(value) => Err(ConfigError::invalid_type(
-                self.origin,
-                Unexpected::U64(value),
-                "an array",
-            )),
-            ValueKind::U128(value) => Err(ConfigError::invalid_type(
-                self.origin,
-                Unexpected::U128(value),
-                "an array",
-            )),
-            ValueKind::Boolean(value) => Err(ConfigError::invalid_type(
-                self.origin,
-                Unexpected::Bool(value),
-                "an array",
-            )),
-            ValueKind::Nil => Err(ConfigError::invalid_type(
-                self.origin,
-                Unexpected::Unit,
-                "an array",
-            )),
-            ValueKind::Table(_) => Err(ConfigError::invalid_type(
-                self.origin,
-                Unexpected::Map,
-                "an array",
-            )),
+            ValueKind::Float(value) => Err(self.invalid_type(Unexpected::Float(value), "an array")),
+            ValueKind::String(ref value) => {
+                Err(self.invalid_type(Unexpected::Str(value.clone()), "an array"))
+            }
+            ValueKind::I64(value) => Err(self.invalid_type(Unexpected::I64(value), "an array")),
+            ValueKind::I128(value) => Err(self.invalid_type(Unexpected::I128(value), "an array")),
+            ValueKind::U64(value) => Err(self.invalid_type(Unexpected::U64(value), "an array")),
+            ValueKind::U128(value) => Err(self.invalid_type(Unexpected::U128(value), "an array")),
+            ValueKind::Boolean(value) => {
+                Err(self.invalid_type(Unexpected::Bool(value), "an array"))
+            }
+            ValueKind::Nil => Err(self.invalid_type(Unexpected::Unit, "an array")),
+            ValueKind::Table(_) => Err(self.invalid_type(Unexpected::Map, "an array")),
         }
     }
 
@@ -658,51 +680,17 @@ impl Value {
             ValueKind::Table(value) => Ok(value),
 
             // Cannot convert
-            ValueKind::Float(value) => Err(ConfigError::invalid_type(
-                self.origin,
-                Unexpected::Float(value),
-                "a map",
-            )),
-            ValueKind::String(value) => Err(ConfigError::invalid_type(
-                self.origin,
-                Unexpected::Str(value),
-                "a map",
-            )),
-            ValueKind::I64(value) => Err(ConfigError::invalid_type(
-                self.origin,
-                Unexpected::I64(value),
-                "a map",
-            )),
-            ValueKind::I128(value) => Err(ConfigError::invalid_type(
-                self.origin,
-                Unexpected::I128(value),
-                "a map",
-            )),
-            ValueKind::U64(value) => Err(ConfigError::invalid_type(
-                self.origin,
-                Unexpected::U64(value),
-                "a map",
-            )),
-            ValueKind::U128(value) => Err(ConfigError::invalid_type(
-                self.origin,
-                Unexpected::U128(value),
-                "a map",
-            )),
-            ValueKind::Boolean(value) => Err(ConfigError::invalid_type(
-                self.origin,
-                Unexpected::Bool(value),
-                "a map",
-            )),
-            ValueKind::Nil => Err(ConfigError::invalid_type(
-                self.origin,
-                Unexpected::Unit,
-                "a map",
-            )),
-            ValueKind::Array(_) => Err(ConfigError::invalid_type(
-                self.origin,
-                Unexpected::Seq,
-                "a map",
-            )),
+            ValueKind::Float(value) => Err(self.invalid_type(Unexpected::Float(value), "a map")),
+            ValueKind::String(ref value) => {
+                Err(self.invalid_type(Unexpected::Str(value.clone()), "a map"))
+            }
+            ValueKind::I64(value) => Err(self.invalid_type(Unexpected::I64(value), "a map")),
+            ValueKind::I128(value) => Err(self.invalid_type(Unexpected::I128(value), "a map")),
+            ValueKind::U64(value) => Err(self.invalid_type(Unexpected::U64(value), "a map")),
+            ValueKind::U128(value) => Err(self.invalid_type(Unexpected::U128(value), "a map")),
+            ValueKind::Boolean(value) => Err(self.invalid_type(Unexpected::Bool(value), "a map")),
+            ValueKind::Nil => Err(self.invalid_type(Unexpected::Unit, "a map")),
+            ValueKind::Array(_) => Err(self.invalid_type(Unexpected::Seq, "a map")),
         }
     }
 }
@@ -862,6 +850,48 @@ impl<'de> Deserialize<'de> for Value {
     }
 }
 
+impl Serialize for ValueKind {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match *self {
+            // Formats without a null (e.g. TOML) skip `None` entries instead of failing
+            Self::Nil => serializer.serialize_none(),
+            Self::Boolean(value) => serializer.serialize_bool(value),
+            Self::I64(value) => serializer.serialize_i64(value),
+            Self::I128(value) => serializer.serialize_i128(value),
+            Self::U64(value) => serializer.serialize_u64(value),
+            Self::U128(value) => serializer.serialize_u128(value),
+            Self::Float(value) => serializer.serialize_f64(value),
+            Self::String(ref value) => serializer.serialize_str(value),
+            Self::Table(ref table) => {
+                let mut map = serializer.serialize_map(Some(table.len()))?;
+                for (key, value) in table {
+                    map.serialize_entry(key, value)?;
+                }
+                map.end()
+            }
+            Self::Array(ref array) => {
+                let mut seq = serializer.serialize_seq(Some(array.len()))?;
+                for value in array {
+                    seq.serialize_element(value)?;
+                }
+                seq.end()
+            }
+        }
+    }
+}
+
+impl Serialize for Value {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.kind.serialize(serializer)
+    }
+}
+
 impl<T> From<T> for Value
 where
     T: Into<ValueKind>,
@@ -869,6 +899,7 @@ where
     fn from(value: T) -> Self {
         Self {
             origin: None,
+            location: None,
             kind: value.into(),
         }
     }
@@ -0,0 +1,513 @@
+use std::collections::HashSet;
+use std::env;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex, RwLock};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::builder::{ConfigBuilder, DefaultState};
+use crate::diff::Diff;
+use crate::error::{ConfigError, Result};
+use crate::path::Expression;
+use crate::value::Value;
+use crate::Config;
+
+/// A configuration rebuilt whenever one of its files changes.
+///
+/// It holds on to the [`ConfigBuilder`] and watches the files and directories its sources are
+/// read from, as listed by [`Source::watch_paths`](crate::Source::watch_paths), including the
+/// files that are not found, such as an optional `config/local` that does not exist yet. Changes
+/// are debounced, so that a burst of writes leads to a single rebuild. If rebuilding fails, the
+/// last good configuration stays current and the error is kept for
+/// [`last_error`](Self::last_error).
+///
+/// ```no_run
+/// # use config::{Config, File, ReloadableConfig};
+/// let settings = ReloadableConfig::new(
+///     Config::builder().add_source(File::with_name("config/Settings")),
+/// )?;
+///
+/// // Each request reads a consistent configuration, even if a reload happens meanwhile
+/// let config = settings.snapshot();
+/// let port: u16 = config.get("server.port")?;
+/// # Ok::<(), config::ConfigError>(())
+/// ```
+///
+/// The watcher stops when the handle is dropped.
+pub struct ReloadableConfig {
+    shared: Arc<Shared>,
+    events: Sender<Message>,
+    thread: Option<JoinHandle<()>>,
+}
+
+/// The state shared by the handle, the thread waiting for changes and the watcher.
+struct Shared {
+    builder: ConfigBuilder<DefaultState>,
+    current: Arc<Current>,
+    last_error: Mutex<Option<Arc<ConfigError>>>,
+    watched: Mutex<Watched>,
+    subscribers: Mutex<Vec<Subscriber>>,
+    next_subscriber: AtomicU64,
+}
+
+/// The current configuration, with a counter of the times it was replaced.
+struct Current {
+    config: RwLock<Arc<Config>>,
+    generation: AtomicU64,
+}
+
+/// The files and directories of the last build, and the directories watched for them.
+struct Watched {
+    watcher: RecommendedWatcher,
+    paths: HashSet<PathBuf>,
+    directories: HashSet<PathBuf>,
+}
+
+enum Message {
+    Changed(notify::Result<notify::Event>),
+    Stop,
+}
+
+impl ReloadableConfig {
+    /// How long the files must stay unchanged before rebuilding, by default.
+    pub const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(100);
+
+    /// Builds the configuration and starts watching its files.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the first build fails, or if the files cannot be watched.
+    pub fn new(builder: ConfigBuilder<DefaultState>) -> Result<Self> {
+        Self::with_debounce(builder, Self::DEFAULT_DEBOUNCE)
+    }
+
+    /// Like [`new`](Self::new), waiting for the files to stay unchanged for `debounce` before
+    /// rebuilding.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the first build fails, or if the files cannot be watched.
+    pub fn with_debounce(builder: ConfigBuilder<DefaultState>, debounce: Duration) -> Result<Self> {
+        let config = builder.build_cloned()?;
+        let paths = builder.watch_paths();
+
+        let (events, receiver) = mpsc::channel();
+        let sender = events.clone();
+        let watcher = notify::recommended_watcher(move |event| {
+            let _ = sender.send(Message::Changed(event));
+        })
+        .map_err(|e| ConfigError::Foreign(Box::new(e)))?;
+
+        let shared = Arc::new(Shared {
+            builder,
+            current: Arc::new(Current {
+                config: RwLock::new(Arc::new(config)),
+                generation: AtomicU64::new(0),
+            }),
+            last_error: Mutex::new(None),
+            watched: Mutex::new(Watched {
+                watcher,
+                paths: HashSet::new(),
+                directories: HashSet::new(),
+            }),
+            subscribers: Mutex::new(Vec::new()),
+            next_subscriber: AtomicU64::new(0),
+        });
+        shared.watched.lock().unwrap().update(paths, true)?;
+
+        let thread = thread::Builder::new()
+            .name("config-watch".into())
+            .spawn({
+                let shared = Arc::clone(&shared);
+                move || shared.run(&receiver, debounce)
+            })
+            .map_err(|e| ConfigError::Foreign(Box::new(e)))?;
+
+        Ok(Self {
+            shared,
+            events,
+            thread: Some(thread),
+        })
+    }
+
+    /// The current configuration.
+    ///
+    /// It stays the same however the files change; call it again for the latest one. Readers
+    /// calling it often should use a [`ConfigReader`] instead.
+    pub fn snapshot(&self) -> Arc<Config> {
+        self.shared.current.load()
+    }
+
+    /// A handle reading the current configuration without taking a lock, unless it was replaced
+    /// since the last read.
+    pub fn reader(&self) -> ConfigReader {
+        let current = Arc::clone(&self.shared.current);
+        let (generation, config) = current.load_with_generation();
+
+        ConfigReader {
+            current,
+            generation,
+            config,
+        }
+    }
+
+    /// Rebuilds the configuration now, without waiting for a change, and tells the subscribers
+    /// about the values that changed.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the rebuild, in which case the current configuration is kept.
+    pub fn reload(&self) -> Result<()> {
+        self.shared.reload()
+    }
+
+    /// Calls `callback` whenever a rebuild changes the value at `key`.
+    ///
+    /// Rebuilds leaving the value as it was, however the rest of the configuration changes, do
+    /// not call it. The value counts as changed when it is set or unset, or when anything in it
+    /// differs, regardless of the files the values come from. The callback runs on the thread
+    /// rebuilding the configuration, once the new one is current.
+    ///
+    /// ```no_run
+    /// # use config::{Config, File, ReloadableConfig};
+    /// # let settings = ReloadableConfig::new(Config::builder().add_source(File::with_name("config/Settings")))?;
+    /// settings.subscribe("log.level", |change| {
+    ///     if let Some(level) = change.new_value() {
+    ///         println!("log level is now {level}");
+    ///     }
+    /// })?;
+    /// # Ok::<(), config::ConfigError>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Fails if `key` is not a valid path expression.
+    pub fn subscribe<F>(&self, key: &str, callback: F) -> Result<()>
+    where
+        F: Fn(&Change) + Send + Sync + 'static,
+    {
+        self.shared
+            .subscribe(key, Notify::Callback(Arc::new(callback)))
+    }
+
+    /// Sends a [`Change`] whenever a rebuild changes the value at `key`, see
+    /// [`subscribe`](Self::subscribe).
+    ///
+    /// The subscription ends when the receiver is dropped.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `key` is not a valid path expression.
+    pub fn subscribe_channel(&self, key: &str) -> Result<Receiver<Change>> {
+        let (sender, receiver) = mpsc::channel();
+        self.shared.subscribe(key, Notify::Channel(sender))?;
+
+        Ok(receiver)
+    }
+
+    /// The error of the last rebuild triggered by a change to the files, if it failed.
+    ///
+    /// It is cleared once a rebuild succeeds.
+    pub fn last_error(&self) -> Option<Arc<ConfigError>> {
+        self.shared.last_error.lock().unwrap().clone()
+    }
+
+    /// The files and directories watched, made absolute.
+    pub fn paths(&self) -> Vec<PathBuf> {
+        let mut paths: Vec<_> = self
+            .shared
+            .watched
+            .lock()
+            .unwrap()
+            .paths
+            .iter()
+            .cloned()
+            .collect();
+        paths.sort();
+        paths
+    }
+}
+
+impl fmt::Debug for ReloadableConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReloadableConfig")
+            .field("config", &self.snapshot())
+            .field("paths", &self.paths())
+            .finish()
+    }
+}
+
+impl Drop for ReloadableConfig {
+    fn drop(&mut self) {
+        let _ = self.events.send(Message::Stop);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Shared {
+    /// Waits for changes to the watched files, rebuilding once they settle.
+    fn run(&self, receiver: &Receiver<Message>, debounce: Duration) {
+        loop {
+            match receiver.recv() {
+                Ok(Message::Changed(event)) if self.is_relevant(&event) => {}
+                Ok(Message::Changed(_)) => continue,
+                Ok(Message::Stop) | Err(_) => return,
+            }
+
+            // Wait for the writes to settle
+            loop {
+                match receiver.recv_timeout(debounce) {
+                    Ok(Message::Changed(_)) => {}
+                    Err(RecvTimeoutError::Timeout) => break,
+                    Ok(Message::Stop) | Err(RecvTimeoutError::Disconnected) => return,
+                }
+            }
+
+            if let Err(error) = self.reload() {
+                *self.last_error.lock().unwrap() = Some(Arc::new(error));
+            }
+        }
+    }
+
+    fn is_relevant(&self, event: &notify::Result<notify::Event>) -> bool {
+        let Ok(event) = event else {
+            return false;
+        };
+        if matches!(event.kind, EventKind::Access(_)) {
+            return false;
+        }
+
+        let watched = self.watched.lock().unwrap();
+        event.paths.iter().any(|path| watched.matches(path))
+    }
+
+    fn reload(&self) -> Result<()> {
+        let (old, new, result) = {
+            let mut watched = self.watched.lock().unwrap();
+            let result = self.builder.build_cloned();
+            let paths = self.builder.watch_paths();
+
+            match result {
+                Ok(config) => {
+                    let old = self.current.load();
+                    let new = self.current.store(config);
+                    *self.last_error.lock().unwrap() = None;
+                    (old, new, watched.update(paths, true))
+                }
+                Err(error) => {
+                    // Keep watching the files of the last good configuration, and the new ones
+                    let _ = watched.update(paths, false);
+                    return Err(error);
+                }
+            }
+        };
+
+        // Without holding locks, subscribers may read the configuration
+        self.notify(&old.cache, &new.cache);
+
+        result
+    }
+
+    fn subscribe(&self, key: &str, notify: Notify) -> Result<()> {
+        let subscriber = Subscriber {
+            id: self.next_subscriber.fetch_add(1, Ordering::Relaxed),
+            key: key.into(),
+            expression: key.parse()?,
+            notify,
+        };
+        self.subscribers.lock().unwrap().push(subscriber);
+
+        Ok(())
+    }
+
+    /// Tells the subscribers about the values that differ between the `old` and `new` configurations.
+    fn notify(&self, old: &Value, new: &Value) {
+        let mut changes = Vec::new();
+        for subscriber in self.subscribers.lock().unwrap().iter() {
+            let old = subscriber.expression.clone().get(old);
+            let new = subscriber.expression.clone().get(new);
+            if !same(old, new) {
+                let change = Change {
+                    key: subscriber.key.clone(),
+                    old: old.cloned(),
+                    new: new.cloned(),
+                };
+                changes.push((subscriber.id, subscriber.notify.clone(), change));
+            }
+        }
+
+        let mut disconnected = Vec::new();
+        for (id, notify, change) in changes {
+            match notify {
+                Notify::Callback(callback) => callback(&change),
+                Notify::Channel(sender) => {
+                    if sender.send(change).is_err() {
+                        disconnected.push(id);
+                    }
+                }
+            }
+        }
+
+        if !disconnected.is_empty() {
+            self.subscribers
+                .lock()
+                .unwrap()
+                .retain(|subscriber| !disconnected.contains(&subscriber.id));
+        }
+    }
+}
+
+/// Whether two values hold the same data, wherever it comes from.
+fn same(a: Option<&Value>, b: Option<&Value>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => Diff::between("", a, b).is_empty(),
+        (a, b) => a.is_none() && b.is_none(),
+    }
+}
+
+impl Current {
+    fn load(&self) -> Arc<Config> {
+        Arc::clone(&self.config.read().unwrap())
+    }
+
+    fn load_with_generation(&self) -> (u64, Arc<Config>) {
+        let config = self.config.read().unwrap();
+        (self.generation.load(Ordering::Acquire), Arc::clone(&config))
+    }
+
+    fn store(&self, config: Config) -> Arc<Config> {
+        let config = Arc::new(config);
+        let mut current = self.config.write().unwrap();
+        *current = Arc::clone(&config);
+        self.generation.fetch_add(1, Ordering::Release);
+
+        config
+    }
+}
+
+impl Watched {
+    /// Whether a change to `path` may change the configuration.
+    fn matches(&self, path: &Path) -> bool {
+        // Files may be named without their extension, directories stand for their entries
+        self.paths.contains(path)
+            || self.paths.contains(&path.with_extension(""))
+            || path
+                .parent()
+                .is_some_and(|parent| self.paths.contains(parent))
+    }
+
+    /// Watches `paths`, which replace the paths watched so far or add to them.
+    ///
+    /// The directories holding the paths are watched rather than the files, to notice files being
+    /// created or replaced, and directories are watched for their entries.
+    fn update(&mut self, paths: Vec<PathBuf>, replace: bool) -> Result<()> {
+        // Events name absolute paths
+        let current = env::current_dir().unwrap_or_default();
+        let paths = paths.into_iter().map(|path| current.join(path));
+        if replace {
+            self.paths = paths.collect();
+        } else {
+            self.paths.extend(paths);
+        }
+
+        let directories: HashSet<PathBuf> = self
+            .paths
+            .iter()
+            .flat_map(|path| [Some(path.as_path()), path.parent()])
+            .flatten()
+            .filter(|directory| directory.is_dir())
+            .map(Path::to_path_buf)
+            .collect();
+
+        for directory in self.directories.difference(&directories) {
+            let _ = self.watcher.unwatch(directory);
+        }
+        let mut result = Ok(());
+        for directory in directories.difference(&self.directories) {
+            if let Err(error) = self.watcher.watch(directory, RecursiveMode::NonRecursive) {
+                result = Err(ConfigError::Foreign(Box::new(error)));
+            }
+        }
+        self.directories = directories;
+
+        result
+    }
+}
+
+/// A subscription to the value at a key.
+struct Subscriber {
+    id: u64,
+    key: String,
+    expression: Expression,
+    notify: Notify,
+}
+
+#[derive(Clone)]
+enum Notify {
+    Callback(Arc<dyn Fn(&Change) + Send + Sync>),
+    Channel(Sender<Change>),
+}
+
+/// A change to the value at a key, as told to the subscribers of a [`ReloadableConfig`].
+#[derive(Clone, Debug)]
+pub struct Change {
+    key: String,
+    old: Option<Value>,
+    new: Option<Value>,
+}
+
+impl Change {
+    /// The key subscribed to.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// The value before the rebuild, unless it was not set.
+    pub fn old_value(&self) -> Option<&Value> {
+        self.old.as_ref()
+    }
+
+    /// The value after the rebuild, unless it is no longer set.
+    pub fn new_value(&self) -> Option<&Value> {
+        self.new.as_ref()
+    }
+}
+
+/// A handle on the current configuration of a [`ReloadableConfig`], for readers.
+///
+/// Created by [`ReloadableConfig::reader`]. Each reader keeps the configuration it last read,
+/// and only takes a lock to fetch the new one once it was replaced, so reading it is as cheap
+/// as checking an atomic counter. It outlives the [`ReloadableConfig`], holding the last
+/// configuration it built.
+#[derive(Clone)]
+pub struct ConfigReader {
+    current: Arc<Current>,
+    generation: u64,
+    config: Arc<Config>,
+}
+
+impl ConfigReader {
+    /// The current configuration.
+    pub fn current(&mut self) -> &Arc<Config> {
+        if self.current.generation.load(Ordering::Acquire) != self.generation {
+            (self.generation, self.config) = self.current.load_with_generation();
+        }
+
+        &self.config
+    }
+}
+
+impl fmt::Debug for ConfigReader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConfigReader")
+            .field("config", &self.config)
+            .finish()
+    }
+}
@@ -3,7 +3,7 @@
 
 use async_trait::async_trait;
 
-use config::{AsyncSource, Config, ConfigError, FileFormat, Format, Map, Value};
+use config::{AsyncSource, Config, ConfigError, FileFormat, Format, Map, Transform, Value};
 
 #[derive(Debug)]
 struct AsyncJson(&'static str);
@@ -183,3 +183,43 @@ async fn test_async_file_sources_with_overrides() {
     );
     assert_eq!(config.get::<i32>("place.number").unwrap(), 1);
 }
+
+#[tokio::test]
+async fn test_async_source_at() {
+    let config = Config::builder()
+        .add_async_source_at("secrets", AsyncJson(r#"{"db": {"password": "hunter2"}}"#))
+        .unwrap()
+        .add_source_at(
+            "database",
+            config::File::from_str(r#"{"host": "localhost"}"#, FileFormat::Json),
+        )
+        .unwrap()
+        .build()
+        .await
+        .unwrap();
+
+    assert_eq!(
+        config.get::<String>("secrets.db.password").unwrap(),
+        "hunter2"
+    );
+    assert_eq!(config.get::<String>("database.host").unwrap(), "localhost");
+}
+
+#[tokio::test]
+async fn test_async_transform() {
+    let config = Config::builder()
+        .add_async_source(
+            Transform::new(AsyncJson(
+                r#"{"DB": {"Host": "localhost", "Password": "hunter2"}}"#,
+            ))
+            .lowercase_keys()
+            .exclude("db.password")
+            .unwrap(),
+        )
+        .build()
+        .await
+        .unwrap();
+
+    assert_eq!(config.get_string("db.host").unwrap(), "localhost");
+    assert!(config.get_string("db.password").is_err());
+}
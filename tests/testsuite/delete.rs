@@ -0,0 +1,101 @@
+use config::{Config, File, FileFormat, DELETE};
+
+#[test]
+fn test_unset() {
+    let c = Config::builder()
+        .set_default("server.port", 8080)
+        .unwrap()
+        .set_default("server.tls.cert", "cert.pem")
+        .unwrap()
+        .set_default("list", vec![1, 2, 3])
+        .unwrap()
+        .unset("server.tls")
+        .unwrap()
+        .unset("list[2]")
+        .unwrap()
+        .unset("missing.key")
+        .unwrap()
+        .build()
+        .unwrap();
+
+    assert_eq!(c.get_int("server.port").unwrap(), 8080);
+    assert!(!c.get_table("server").unwrap().contains_key("tls"));
+    assert_eq!(c.get::<Vec<i32>>("list").unwrap(), vec![1, 2]);
+    assert!(c.get_table("missing").is_err());
+}
+
+#[test]
+#[cfg(feature = "toml")]
+fn test_delete_in_source() {
+    let c = Config::builder()
+        .add_source(File::from_str(
+            r#"
+            debug = true
+
+            [server]
+            port = 8080
+
+            [server.tls]
+            cert = "cert.pem"
+            "#,
+            FileFormat::Toml,
+        ))
+        .add_source(File::from_str(
+            &format!(
+                r#"
+                debug = "{DELETE}"
+
+                [server]
+                tls = "{DELETE}"
+                "#
+            ),
+            FileFormat::Toml,
+        ))
+        .build()
+        .unwrap();
+
+    assert!(c.get_bool("debug").is_err());
+    assert_eq!(c.get_int("server.port").unwrap(), 8080);
+    assert!(!c.get_table("server").unwrap().contains_key("tls"));
+}
+
+#[test]
+#[cfg(feature = "yaml")]
+fn test_delete_yaml_tag() {
+    let c = Config::builder()
+        .add_source(File::from_str(
+            "server:\n  port: 8080\n  tls:\n    cert: cert.pem\n",
+            FileFormat::Yaml,
+        ))
+        .add_source(File::from_str(
+            "server:\n  tls: !delete\n",
+            FileFormat::Yaml,
+        ))
+        .build()
+        .unwrap();
+
+    assert_eq!(c.get_int("server.port").unwrap(), 8080);
+    assert!(!c.get_table("server").unwrap().contains_key("tls"));
+}
+
+#[test]
+#[cfg(feature = "toml")]
+fn test_delete_later_source_sets_again() {
+    let c = Config::builder()
+        .set_default("server.tls.cert", "default.pem")
+        .unwrap()
+        .add_source(File::from_str(
+            r#"server = { tls = "!delete" }"#,
+            FileFormat::Toml,
+        ))
+        .add_source(File::from_str(
+            r#"server = { tls = { key = "key.pem" } }"#,
+            FileFormat::Toml,
+        ))
+        .build()
+        .unwrap();
+
+    // Later layers set the key again, without the values deleted before
+    assert_eq!(c.get_string("server.tls.key").unwrap(), "key.pem");
+    assert!(c.get_string("server.tls.cert").is_err());
+}
@@ -0,0 +1,2 @@
+port = 80
+host = 
@@ -0,0 +1,265 @@
+#![cfg(feature = "diagnostics")]
+
+use snapbox::{assert_data_eq, str};
+
+use config::{Config, File, FileFormat};
+
+#[test]
+#[cfg(feature = "toml")]
+fn test_diagnostic_type() {
+    let c = Config::builder()
+        .add_source(File::with_name("tests/testsuite/location.toml"))
+        .build()
+        .unwrap();
+
+    let err = c.get::<u16>("database.port").unwrap_err();
+
+    assert_data_eq!(
+        err.diagnostic().to_string(),
+        str![[r#"
+error: invalid type: string "not a port", expected an integer
+ --> tests/testsuite/location.toml:6:8
+  |
+6 | port = "not a port"
+  |        ^^^^^^^^^^^^ expected an integer, found string "not a port"
+  |
+  = key: database.port
+"#]]
+    );
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_diagnostic_type_without_file() {
+    let c = Config::builder()
+        .add_source(File::from_str(r#"{"debug": "fals"}"#, FileFormat::Json))
+        .build()
+        .unwrap();
+
+    let err = c.get::<bool>("debug").unwrap_err();
+
+    assert_data_eq!(
+        err.diagnostic().to_string(),
+        str![[r#"
+error: invalid type: string "fals", expected a boolean
+  = key: debug
+"#]]
+    );
+}
+
+#[test]
+#[cfg(feature = "toml")]
+fn test_diagnostic_type_file_changed() {
+    let path = std::env::temp_dir().join(format!(
+        "config-diagnostic-{}-changed.toml",
+        std::process::id()
+    ));
+    std::fs::write(&path, "port = \"not a port\"\n").unwrap();
+
+    let c = Config::builder()
+        .add_source(File::from(path.as_path()))
+        .build()
+        .unwrap();
+    let err = c.get::<u16>("port").unwrap_err();
+
+    // The source is quoted as it was read, not as it is now
+    std::fs::write(&path, "port = 8080\n").unwrap();
+    let diagnostic = err.diagnostic().to_string();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_data_eq!(
+        diagnostic,
+        str![[r#"
+error: invalid type: string "not a port", expected an integer
+ --> [..]config-diagnostic-[..]-changed.toml:1:8
+  |
+1 | port = "not a port"
+  |        ^^^^^^^^^^^^ expected an integer, found string "not a port"
+  |
+  = key: port
+"#]]
+    );
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_diagnostic_parse() {
+    let err = Config::builder()
+        .add_source(File::with_name("tests/testsuite/diagnostic-invalid.json"))
+        .build()
+        .unwrap_err();
+
+    assert_data_eq!(
+        err.diagnostic().to_string(),
+        str![[r#"
+error: expected `,` or `}`
+ --> tests/testsuite/diagnostic-invalid.json:3:14
+  |
+3 |   "port": 80 80
+  |              ^
+  |
+"#]]
+    );
+}
+
+#[test]
+#[cfg(feature = "dotenv")]
+fn test_diagnostic_parse_dotenv() {
+    let err = Config::builder()
+        .add_source(File::new(
+            "tests/testsuite/diagnostic-invalid.env",
+            FileFormat::Dotenv,
+        ))
+        .build()
+        .unwrap_err();
+
+    assert_data_eq!(
+        err.diagnostic().to_string(),
+        str![[r#"
+error: unterminated double-quoted value
+ --> tests/testsuite/diagnostic-invalid.env:2:6
+  |
+2 | NAME="unterminated
+  |      ^
+  |
+"#]]
+    );
+}
+
+#[test]
+#[cfg(feature = "properties")]
+fn test_diagnostic_parse_properties() {
+    let err = Config::builder()
+        .add_source(File::with_name(
+            "tests/testsuite/diagnostic-invalid.properties",
+        ))
+        .build()
+        .unwrap_err();
+
+    assert_data_eq!(
+        err.diagnostic().to_string(),
+        str![[r#"
+error: expected 4 hexadecimal digits in `/u` escape
+ --> tests/testsuite/diagnostic-invalid.properties:2:17
+  |
+2 | greeting=caf/u00zz
+  |                 ^
+  |
+"#]]
+    );
+}
+
+#[test]
+#[cfg(feature = "hcl")]
+fn test_diagnostic_parse_hcl() {
+    let err = Config::builder()
+        .add_source(File::with_name("tests/testsuite/diagnostic-invalid.hcl"))
+        .build()
+        .unwrap_err();
+
+    assert_data_eq!(
+        err.diagnostic().to_string(),
+        str![[r#"
+error: invalid expression; expected `"`, `[`, `{`, `-`, `!`, `(`, `_`, `<`, letter or digit
+ --> tests/testsuite/diagnostic-invalid.hcl:2:8
+  |
+2 | host = 
+  |        ^
+  |
+"#]]
+    );
+}
+
+#[test]
+#[cfg(feature = "kdl")]
+fn test_diagnostic_parse_kdl() {
+    let err = Config::builder()
+        .add_source(File::with_name("tests/testsuite/diagnostic-invalid.kdl"))
+        .build()
+        .unwrap_err();
+
+    assert_data_eq!(
+        err.diagnostic().to_string(),
+        str![[r#"
+error: Non-digit character found after the '.' of a float
+ --> tests/testsuite/diagnostic-invalid.kdl:2:7
+  |
+2 | ratio 1.
+  |       ^^
+  |
+"#]]
+    );
+}
+
+#[test]
+fn test_diagnostic_other() {
+    let c = Config::default();
+    let err = c.get::<bool>("debug").unwrap_err();
+
+    assert_data_eq!(
+        err.diagnostic().to_string(),
+        str![[r#"error: configuration property "debug" not found"#]]
+    );
+}
+
+#[test]
+#[cfg(feature = "toml")]
+fn test_diagnostic_multiple() {
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Database {
+        port: u16,
+        user: String,
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Settings {
+        database: Database,
+    }
+
+    let err = Config::builder()
+        .add_source(File::with_name("tests/testsuite/location.toml"))
+        .build()
+        .unwrap()
+        .try_deserialize_collecting_errors::<Settings>()
+        .unwrap_err();
+
+    assert_data_eq!(
+        err.diagnostic().to_string(),
+        str![[r#"
+error: invalid type: string "not a port", expected an integer
+ --> tests/testsuite/location.toml:6:8
+  |
+6 | port = "not a port"
+  |        ^^^^^^^^^^^^ expected an integer, found string "not a port"
+  |
+  = key: database.port
+
+error: missing field `user` for key `database`
+"#]]
+    );
+}
+
+#[test]
+#[cfg(all(feature = "toml", feature = "json"))]
+fn test_diagnostic_include() {
+    let err = Config::builder()
+        .add_source(File::with_name("tests/testsuite/include/broken-main").include_key("include"))
+        .build()
+        .unwrap_err();
+
+    assert_data_eq!(
+        err.diagnostic().to_string(),
+        str![[r#"
+error: expected `,` or `}`
+ --> tests/testsuite/include/broken.json:2:14
+  |
+2 |   "port": 80 80
+  |              ^
+  |
+  = included in tests/testsuite/include/broken.toml
+  = included in tests/testsuite/include/broken-main.toml
+"#]]
+    );
+}
@@ -0,0 +1,144 @@
+#![cfg(feature = "toml")]
+
+use snapbox::{assert_data_eq, str};
+
+use config::{Config, DiffKind, File};
+
+fn load(name: &str) -> Config {
+    Config::builder()
+        .add_source(File::with_name(name))
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_diff() {
+    let old = load("tests/testsuite/diff/old");
+    let new = load("tests/testsuite/diff/new");
+
+    let diff = old.diff(&new);
+    let entries: Vec<_> = diff
+        .entries()
+        .iter()
+        .map(|entry| (entry.key(), entry.kind()))
+        .collect();
+    assert_eq!(
+        entries,
+        vec![
+            ("debug", DiffKind::Removed),
+            ("features[1]", DiffKind::Removed),
+            ("server.port", DiffKind::Changed),
+            ("server.tls", DiffKind::Added),
+        ]
+    );
+
+    let port = &diff.entries()[2];
+    assert_eq!(port.old_value().unwrap().clone().into_int().unwrap(), 8080);
+    assert_eq!(port.new_value().unwrap().clone().into_int().unwrap(), 9090);
+    assert_eq!(
+        port.new_value().unwrap().origin(),
+        Some("tests/testsuite/diff/new.toml")
+    );
+}
+
+#[test]
+fn test_diff_display() {
+    let old = load("tests/testsuite/diff/old");
+    let new = load("tests/testsuite/diff/new");
+
+    assert_data_eq!(
+        old.diff(&new).to_string(),
+        str![[r#"
+- debug = true (tests/testsuite/diff/old.toml:1:9)
+- features[1] = "b" (tests/testsuite/diff/old.toml:2:18)
+~ server.port = 8080 -> 9090 (tests/testsuite/diff/old.toml:6:8 -> tests/testsuite/diff/new.toml:5:8)
++ server.tls = true (tests/testsuite/diff/new.toml:6:7)
+"#]]
+    );
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_diff_json() {
+    let old = Config::builder()
+        .set_default("port", 8080)
+        .unwrap()
+        .build()
+        .unwrap();
+    let new = Config::builder()
+        .set_default("port", 9090)
+        .unwrap()
+        .set_default("tls", true)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    assert_data_eq!(
+        old.diff(&new).to_json(),
+        str![[r#"
+[
+  {
+    "key": "port",
+    "change": "changed",
+    "old": {
+      "value": 8080,
+      "origin": null
+    },
+    "new": {
+      "value": 9090,
+      "origin": null
+    }
+  },
+  {
+    "key": "tls",
+    "change": "added",
+    "old": null,
+    "new": {
+      "value": true,
+      "origin": null
+    }
+  }
+]
+"#]]
+    );
+}
+
+#[test]
+fn test_diff_scalar_types() {
+    let old = Config::builder()
+        .set_override("port", 8080)
+        .unwrap()
+        .set_override("debug", true)
+        .unwrap()
+        .set_override("ratio", 1.5)
+        .unwrap()
+        .set_override("name", "nil")
+        .unwrap()
+        .build()
+        .unwrap();
+    let new = Config::builder()
+        .set_override("port", "8080")
+        .unwrap()
+        .set_override("debug", "true")
+        .unwrap()
+        .set_override("ratio", "1.5")
+        .unwrap()
+        .set_override("name", None::<String>)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    // Scalars of different types reading the same are the same, unless nil
+    assert_data_eq!(
+        old.diff(&new).to_string(),
+        str![[r#"~ name = "nil" -> nil"#]]
+    );
+}
+
+#[test]
+fn test_diff_same() {
+    let old = load("tests/testsuite/diff/old");
+
+    assert!(old.diff(&old).is_empty());
+    assert_eq!(old.diff(&old).to_string(), "");
+}
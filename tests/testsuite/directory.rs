@@ -0,0 +1,109 @@
+#![cfg(all(feature = "toml", feature = "json", feature = "yaml"))]
+
+use config::{Config, Directory, DirectoryOrder};
+
+const CONF_D: &str = "tests/testsuite/directory/conf.d";
+
+#[test]
+fn test_lexical_order() {
+    let c = Config::builder()
+        .add_source(Directory::new(CONF_D))
+        .build()
+        .unwrap();
+
+    // 10-override.json comes before 9-base.toml
+    assert_eq!(c.get_int("port").unwrap(), 8080);
+    assert_eq!(c.get_int("pool.size").unwrap(), 4);
+    assert_eq!(c.get_int("pool.timeout").unwrap(), 30);
+    assert_eq!(c.get_string("name").unwrap(), "extra");
+    // Neither subdirectories nor hidden files are read
+    assert!(c.get_bool("debug").is_err());
+}
+
+#[test]
+fn test_numeric_order() {
+    let c = Config::builder()
+        .add_source(Directory::new(CONF_D).order(DirectoryOrder::Numeric))
+        .build()
+        .unwrap();
+
+    assert_eq!(c.get_int("port").unwrap(), 9090);
+    assert_eq!(c.get_int("pool.size").unwrap(), 4);
+    // Files without a number come last
+    assert_eq!(c.get_string("name").unwrap(), "extra");
+}
+
+#[test]
+fn test_recursive() {
+    let c = Config::builder()
+        .add_source(Directory::new(CONF_D).recursive(true))
+        .build()
+        .unwrap();
+
+    assert!(c.get_bool("debug").unwrap());
+    assert_eq!(c.get_int("port").unwrap(), 8080);
+}
+
+#[test]
+fn test_nested() {
+    let c = Config::builder()
+        .add_source(
+            Directory::new(CONF_D)
+                .order(DirectoryOrder::Numeric)
+                .recursive(true)
+                .nested(true),
+        )
+        .build()
+        .unwrap();
+
+    assert_eq!(c.get_int("base.port").unwrap(), 8080);
+    assert_eq!(c.get_int("override.port").unwrap(), 9090);
+    assert_eq!(c.get_string("extra.name").unwrap(), "extra");
+    assert!(c.get_bool("sub.nested.debug").unwrap());
+    assert!(c.get_int("port").is_err());
+}
+
+#[test]
+fn test_nested_lexical_keeps_numbers() {
+    let c = Config::builder()
+        .add_source(Directory::new(CONF_D).nested(true))
+        .build()
+        .unwrap();
+
+    assert_eq!(c.get_int("9-base.port").unwrap(), 8080);
+    assert_eq!(c.get_int("10-override.port").unwrap(), 9090);
+}
+
+#[test]
+fn test_merges_over_earlier_sources() {
+    let c = Config::builder()
+        .set_default("port", 80)
+        .unwrap()
+        .set_default("host", "localhost")
+        .unwrap()
+        .add_source(Directory::new(CONF_D))
+        .build()
+        .unwrap();
+
+    assert_eq!(c.get_int("port").unwrap(), 8080);
+    assert_eq!(c.get_string("host").unwrap(), "localhost");
+}
+
+#[test]
+fn test_missing_directory() {
+    let res = Config::builder()
+        .add_source(Directory::new("tests/testsuite/directory/missing"))
+        .build();
+
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("configuration directory \"tests/testsuite/directory/missing\" not found"));
+
+    let c = Config::builder()
+        .add_source(Directory::new("tests/testsuite/directory/missing").required(false))
+        .build()
+        .unwrap();
+
+    assert!(c.get_int("port").is_err());
+}
@@ -270,3 +270,124 @@ fn test_error_root_not_table() {
         },
     }
 }
+
+#[test]
+#[cfg(feature = "toml")]
+fn test_error_collect_all() {
+    #[derive(Deserialize, Debug)]
+    #[allow(dead_code)]
+    struct Database {
+        url: String,
+        port: u16,
+        user: String,
+        pool: Vec<u32>,
+    }
+
+    #[derive(Deserialize, Debug)]
+    #[allow(dead_code)]
+    struct Settings {
+        name: String,
+        debug: bool,
+        timeout: Option<u32>,
+        database: Database,
+        cache: Map<String, String>,
+    }
+
+    let e = Config::builder()
+        .add_source(File::with_name("tests/testsuite/location.toml"))
+        .set_override("database.pool", vec!["1", "two", "3"])
+        .unwrap()
+        .set_override("timeout", "soon")
+        .unwrap()
+        .set_override("cache", "none")
+        .unwrap()
+        .build()
+        .unwrap()
+        .try_deserialize_collecting_errors::<Settings>()
+        .unwrap_err();
+
+    assert_data_eq!(
+        e.to_string(),
+        str![[r#"
+found 6 errors in the configuration:
+  invalid type: string "none", expected a map for key `cache`
+  invalid type: string "two", expected an integer for key `database.pool[1]`
+  invalid type: string "not a port", expected an integer for key `database.port` in tests/testsuite/location.toml:6:8
+  missing field `user` for key `database`
+  missing field `debug`
+  invalid type: string "soon", expected an integer for key `timeout`
+"#]]
+    );
+
+    match e {
+        ConfigError::Multiple(ref errors) => assert!(matches!(
+            errors[3],
+            ConfigError::MissingField { field: "user", .. }
+        )),
+        _ => panic!("Wrong error: {:?}", e),
+    }
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_error_collect_none() {
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Settings {
+        name: String,
+        tags: Vec<String>,
+        port: Option<u16>,
+    }
+
+    let c = Config::builder()
+        .add_source(File::from_str(
+            r#"{"name": "app", "tags": ["a", "b"]}"#,
+            FileFormat::Json,
+        ))
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        c.clone()
+            .try_deserialize_collecting_errors::<Settings>()
+            .unwrap(),
+        c.try_deserialize::<Settings>().unwrap()
+    );
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_error_collect_invalid_table() {
+    #[derive(Deserialize, Debug)]
+    #[allow(dead_code)]
+    struct Inner {
+        a: u8,
+        b: u8,
+    }
+
+    #[derive(Deserialize, Debug)]
+    #[allow(dead_code)]
+    struct Outer {
+        inner: Inner,
+        list: Vec<Inner>,
+    }
+
+    let e = Config::builder()
+        .add_source(File::from_str(
+            r#"{"inner": "nope", "list": [{"a": 1}, {"a": 1, "b": 300}]}"#,
+            FileFormat::Json,
+        ))
+        .build()
+        .unwrap()
+        .try_deserialize_collecting_errors::<Outer>()
+        .unwrap_err();
+
+    assert_data_eq!(
+        e.to_string(),
+        str![[r#"
+found 3 errors in the configuration:
+  invalid type: string "nope", expected a map for key `inner`
+  missing field `b` for key `list[0]`
+  invalid type: 64-bit unsigned integer `300`, expected an unsigned 8 bit integer for key `list[1]b`
+"#]]
+    );
+}
@@ -0,0 +1,112 @@
+#![cfg(feature = "json")]
+
+use snapbox::{assert_data_eq, str};
+
+use config::{Config, ConfigError, File, FileFormat, Layer};
+
+fn layered() -> Config {
+    Config::builder()
+        .set_default("db.port", 5432)
+        .unwrap()
+        .set_default("db.host", "localhost")
+        .unwrap()
+        .add_source(File::with_name("tests/testsuite/file-auto"))
+        .add_source(File::from_str(
+            r#"{"db": {"port": 5433}, "production": true}"#,
+            FileFormat::Json,
+        ))
+        .set_override("debug", false)
+        .unwrap()
+        .track_layers(true)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_explain_shadowed() {
+    let c = layered();
+    let explanation = c.explain("db.port").unwrap();
+
+    assert_eq!(explanation.key(), "db.port");
+    assert_eq!(explanation.value().clone().into_int().unwrap(), 5433);
+
+    let winner = explanation.winner().unwrap();
+    assert_eq!(
+        winner.layer(),
+        &Layer::Source {
+            index: 1,
+            description: "string".to_owned()
+        }
+    );
+    assert_eq!(winner.origin(), None);
+
+    let shadowed = explanation.shadowed();
+    assert_eq!(shadowed.len(), 1);
+    assert_eq!(shadowed[0].layer(), &Layer::Default);
+    assert_eq!(shadowed[0].value().clone().into_int().unwrap(), 5432);
+}
+
+#[test]
+fn test_explain_single_layer() {
+    let c = layered();
+    let explanation = c.explain("db.host").unwrap();
+
+    assert_eq!(explanation.layers().len(), 1);
+    assert_eq!(explanation.winner().unwrap().layer(), &Layer::Default);
+    assert!(explanation.shadowed().is_empty());
+}
+
+#[test]
+fn test_explain_origin() {
+    let c = layered();
+    let explanation = c.explain("production").unwrap();
+
+    assert_eq!(
+        explanation.shadowed()[0].layer(),
+        &Layer::Source {
+            index: 0,
+            description: r#"file "tests/testsuite/file-auto""#.to_owned()
+        }
+    );
+    assert_eq!(
+        explanation.shadowed()[0].origin(),
+        Some("tests/testsuite/file-auto.json")
+    );
+}
+
+#[test]
+fn test_explain_display() {
+    let c = layered();
+
+    assert_data_eq!(
+        c.explain("debug").unwrap().to_string(),
+        str![[r#"
+debug = false
+  false from override
+  true from file "tests/testsuite/file-auto" (tests/testsuite/file-auto.json), shadowed
+"#]]
+    );
+}
+
+#[test]
+fn test_explain_not_found() {
+    let c = layered();
+    let res = c.explain("db.user");
+
+    assert!(matches!(res, Err(ConfigError::NotFound(..))));
+}
+
+#[test]
+fn test_explain_untracked() {
+    let c = Config::builder()
+        .set_default("db.port", 5432)
+        .unwrap()
+        .build()
+        .unwrap();
+    let res = c.explain("db.port");
+
+    assert_data_eq!(
+        res.unwrap_err().to_string(),
+        str!["explaining keys requires building with `ConfigBuilder::track_layers`"]
+    );
+}
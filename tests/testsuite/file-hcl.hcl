@@ -0,0 +1,34 @@
+# Settings of the deployment
+region  = "eu-west-1"
+debug   = false
+timeout = 2 * 60
+zones   = ["a", "b"]
+name    = "${var.prefix}-db"
+
+terraform {
+  backend "s3" {
+    bucket = "logs"
+    key    = "state/prod"
+  }
+}
+
+resource "aws_instance" "web" {
+  ami  = "ami-123"
+  tags = { Name = "web", Tier = var.tier }
+}
+
+resource "aws_instance" "worker" {
+  ami = "ami-456"
+}
+
+ingress {
+  port = 80
+}
+
+ingress {
+  port = 443
+}
+
+motd = <<EOT
+Welcome
+EOT
@@ -0,0 +1,159 @@
+#![cfg(feature = "dotenv")]
+
+use snapbox::{assert_data_eq, str};
+
+use config::{Config, Environment, File, FileFormat, Map, Value};
+
+fn parse(text: &str) -> Config {
+    Config::builder()
+        .add_source(File::from_str(text, FileFormat::Dotenv))
+        .track_layers(true)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_file() {
+    let c = parse(
+        r#"
+# A comment
+DEBUG=true
+export PORT = 8080
+NAME=Torre di Pisa # trailing comment
+EMPTY=
+HASH=a#b
+SINGLE='no ${DEBUG} or \n here'
+DOUBLE="tab\tquote\" dollar\$ newline\n"
+MULTI="first
+second"
+"#,
+    );
+
+    assert!(c.get_bool("DEBUG").unwrap());
+    assert_eq!(c.get_int("PORT").unwrap(), 8080);
+    assert_eq!(c.get_string("NAME").unwrap(), "Torre di Pisa");
+    assert_eq!(c.get_string("EMPTY").unwrap(), "");
+    assert_eq!(c.get_string("HASH").unwrap(), "a#b");
+    assert_eq!(c.get_string("SINGLE").unwrap(), r"no ${DEBUG} or \n here");
+    assert_eq!(
+        c.get_string("DOUBLE").unwrap(),
+        "tab\tquote\" dollar$ newline\n"
+    );
+    assert_eq!(c.get_string("MULTI").unwrap(), "first\nsecond");
+}
+
+#[test]
+fn test_references() {
+    temp_env::with_vars(
+        [("DOTENV_USER", Some("admin")), ("DOTENV_HOST", Some("env"))],
+        || {
+            let c = parse(
+                r#"
+DOTENV_HOST=localhost
+URL="http://${DOTENV_USER}@${DOTENV_HOST}:${PORT}/"
+PORT=80
+PLAIN=$HOME
+"#,
+            );
+
+            // Earlier lines win over the environment, later lines are not seen yet
+            assert_eq!(c.get_string("URL").unwrap(), "http://admin@localhost:/");
+            assert_eq!(c.get_string("PLAIN").unwrap(), "$HOME");
+        },
+    );
+}
+
+#[test]
+fn test_location() {
+    let c = parse("A=1\n  B = \"two\"\n");
+
+    let location = c.explain("B").unwrap().value().location().unwrap();
+    assert_eq!((location.line(), location.column()), (2, 7));
+}
+
+#[test]
+fn test_error_parse() {
+    let res = Config::builder()
+        .add_source(File::from_str(
+            "OK=1\nNAME=\"unterminated\n",
+            FileFormat::Dotenv,
+        ))
+        .build();
+
+    assert_data_eq!(
+        res.unwrap_err().to_string(),
+        str!["unterminated double-quoted value at line 2 column 6"]
+    );
+
+    let res = Config::builder()
+        .add_source(File::from_str("NOT A VARIABLE\n", FileFormat::Dotenv))
+        .build();
+
+    assert_data_eq!(
+        res.unwrap_err().to_string(),
+        str!["expected `=` after `NOT` at line 1 column 5"]
+    );
+}
+
+#[test]
+fn test_render() {
+    let mut values = Map::new();
+    let mut server = Map::new();
+    server.insert("port".to_owned(), Value::from(8080));
+    values.insert("server".to_owned(), Value::from(server));
+    values.insert("name".to_owned(), Value::from("say \"hi\"\n$5"));
+    values.insert("debug".to_owned(), Value::from(true));
+
+    let text = config::FormatWriter::render(&FileFormat::Dotenv, &values).unwrap();
+    assert_eq!(
+        text,
+        "debug=true\nname=\"say \\\"hi\\\"\\n\\$5\"\nserver.port=8080\n"
+    );
+
+    let c = parse(&text);
+    assert_eq!(c.get_string("name").unwrap(), "say \"hi\"\n$5");
+    assert_eq!(c.get_int("server.port").unwrap(), 8080);
+}
+
+#[test]
+fn test_environment() {
+    let c = Config::builder()
+        .add_source(
+            Environment::with_prefix("APP")
+                .separator("__")
+                .prefix_separator("_")
+                .try_parsing(true)
+                .dotenv("tests/testsuite/dotenv/.env.local"),
+        )
+        .track_layers(true)
+        .build()
+        .unwrap();
+
+    assert!(c.get_bool("debug").unwrap());
+    assert_eq!(c.get::<i64>("server.port").unwrap(), 8080);
+    assert_eq!(c.get_string("server.host").unwrap(), "localhost");
+    assert_eq!(c.get_string("name").unwrap(), "My App");
+    assert_eq!(c.get_string("url").unwrap(), "http://localhost:8080");
+    assert!(c.get_string("other").is_err());
+    assert_eq!(
+        c.explain("name").unwrap().winner().unwrap().origin(),
+        Some("tests/testsuite/dotenv/.env.local")
+    );
+}
+
+#[test]
+fn test_environment_missing_file() {
+    temp_env::with_var("APP_DEBUG", Some("true"), || {
+        let c = Config::builder()
+            .add_source(
+                Environment::with_prefix("APP")
+                    .try_parsing(true)
+                    .dotenv("tests/testsuite/dotenv/.env.missing"),
+            )
+            .build()
+            .unwrap();
+
+        // The environment is read when the file is missing
+        assert!(c.get_bool("debug").unwrap());
+    });
+}
@@ -0,0 +1,173 @@
+#![cfg(feature = "hcl")]
+
+use serde_derive::Deserialize;
+use snapbox::{assert_data_eq, str};
+
+use config::{Config, File, FileFormat, FormatWriter, Map, Value};
+
+fn config() -> Config {
+    Config::builder()
+        .add_source(File::new("tests/testsuite/file-hcl", FileFormat::Hcl))
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_file() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Settings {
+        region: String,
+        debug: bool,
+        timeout: u32,
+        zones: Vec<String>,
+        ingress: Vec<Ingress>,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Ingress {
+        port: u16,
+    }
+
+    let s: Settings = config().try_deserialize().unwrap();
+    assert_eq!(
+        s,
+        Settings {
+            region: "eu-west-1".to_owned(),
+            debug: false,
+            timeout: 120,
+            zones: vec!["a".to_owned(), "b".to_owned()],
+            ingress: vec![Ingress { port: 80 }, Ingress { port: 443 }],
+        }
+    );
+}
+
+#[test]
+fn test_labeled_blocks() {
+    let c = config();
+
+    assert_eq!(c.get_string("terraform.backend.s3.bucket").unwrap(), "logs");
+    assert_eq!(
+        c.get_string("resource.aws_instance.web.ami").unwrap(),
+        "ami-123"
+    );
+    assert_eq!(
+        c.get_string("resource.aws_instance.worker.ami").unwrap(),
+        "ami-456"
+    );
+    assert_eq!(
+        c.get_string("resource.aws_instance.web.tags.Name").unwrap(),
+        "web"
+    );
+}
+
+#[test]
+fn test_expressions() {
+    let c = config();
+
+    assert_eq!(c.get_string("name").unwrap(), "${var.prefix}-db");
+    assert_eq!(
+        c.get_string("resource.aws_instance.web.tags.Tier").unwrap(),
+        "var.tier"
+    );
+    assert_eq!(c.get_string("motd").unwrap(), "Welcome\n");
+}
+
+#[test]
+fn test_error_parse() {
+    let res = Config::builder()
+        .add_source(File::from_str("port = 80\nhost = \n", FileFormat::Hcl))
+        .build();
+
+    assert_data_eq!(
+        res.unwrap_err().to_string(),
+        str![[r#"
+ --> HCL parse error in line 2, column 8
+  |
+2 | host = 
+  |        ^---
+  |
+  = invalid expression; expected `"`, `[`, `{`, `-`, `!`, `(`, `_`, `<`, letter or digit
+"#]]
+    );
+}
+
+#[test]
+fn test_error_block_conflict() {
+    let parse = |text: &str| {
+        Config::builder()
+            .add_source(File::from_str(text, FileFormat::Hcl))
+            .build()
+            .unwrap_err()
+            .to_string()
+    };
+
+    assert_data_eq!(
+        parse("ingress {\n  port = 80\n}\ningress {\n  port = 443\n}\ningress \"x\" {}\n"),
+        str!["block `ingress.x` conflicts with the value at `ingress`"]
+    );
+    assert_data_eq!(
+        parse("backend = \"local\"\nbackend {}\n"),
+        str!["block `backend` conflicts with the value at `backend`"]
+    );
+    assert_data_eq!(
+        parse("backend {\n  path = \"a\"\n}\nbackend = \"local\"\n"),
+        str!["attribute `backend` conflicts with the value at `backend`"]
+    );
+}
+
+#[test]
+fn test_render() {
+    let mut s3 = Map::new();
+    s3.insert("bucket".to_owned(), Value::from("logs"));
+    let mut backend = Map::new();
+    backend.insert("s3".to_owned(), Value::from(s3));
+    let mut ingress = Map::new();
+    ingress.insert("port".to_owned(), Value::from(80));
+    let mut values = Map::new();
+    values.insert("backend".to_owned(), Value::from(backend));
+    values.insert(
+        "ingress".to_owned(),
+        Value::from(vec![Value::from(ingress.clone()), Value::from(ingress)]),
+    );
+    values.insert(
+        "zones".to_owned(),
+        Value::from(vec![Value::from("a"), Value::from("b")]),
+    );
+    values.insert("debug".to_owned(), Value::from(true));
+
+    let text = FileFormat::Hcl.render(&values).unwrap();
+    assert_data_eq!(
+        text.clone(),
+        str![[r#"
+backend {
+  s3 {
+    bucket = "logs"
+  }
+}
+
+debug = true
+
+ingress {
+  port = 80
+}
+
+ingress {
+  port = 80
+}
+
+zones = [
+  "a",
+  "b"
+]
+
+"#]]
+    );
+
+    let c = Config::builder()
+        .add_source(File::from_str(&text, FileFormat::Hcl))
+        .build()
+        .unwrap();
+    assert_eq!(c.get_string("backend.s3.bucket").unwrap(), "logs");
+    assert_eq!(c.get_int("ingress[1].port").unwrap(), 80);
+    assert!(c.get_bool("debug").unwrap());
+}
@@ -0,0 +1,171 @@
+#![cfg(feature = "kdl")]
+
+use serde_derive::Deserialize;
+use snapbox::{assert_data_eq, str};
+
+use config::{Config, File, FileFormat, FormatWriter, Map, Value};
+
+fn config() -> Config {
+    Config::builder()
+        .add_source(File::new("tests/testsuite/file-kdl", FileFormat::Kdl))
+        .track_layers(true)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_file() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Settings {
+        version: u32,
+        debug: bool,
+        ratio: f64,
+        zones: Vec<String>,
+        retries: Option<u32>,
+        server: Server,
+        user: Vec<User>,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Server {
+        args: Vec<String>,
+        port: u16,
+        tls: bool,
+        host: String,
+        alias: Vec<String>,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct User {
+        name: String,
+        #[serde(default)]
+        admin: bool,
+    }
+
+    let s: Settings = config().try_deserialize().unwrap();
+    assert_eq!(
+        s,
+        Settings {
+            version: 2,
+            debug: false,
+            ratio: 0.5,
+            zones: vec!["a".to_owned(), "b".to_owned()],
+            retries: None,
+            server: Server {
+                args: vec!["main".to_owned()],
+                port: 8080,
+                tls: true,
+                host: "localhost".to_owned(),
+                alias: vec!["www".to_owned(), "api".to_owned()],
+            },
+            user: vec![
+                User {
+                    name: "alice".to_owned(),
+                    admin: true,
+                },
+                User {
+                    name: "bob".to_owned(),
+                    admin: false,
+                },
+            ],
+        }
+    );
+}
+
+#[test]
+fn test_empty_children() {
+    assert!(config().get_table("cache").unwrap().is_empty());
+}
+
+#[test]
+fn test_location() {
+    let location = config()
+        .explain("server.port")
+        .unwrap()
+        .value()
+        .location()
+        .unwrap();
+
+    assert_eq!((location.line(), location.column()), (8, 15));
+}
+
+#[test]
+fn test_error_parse() {
+    let res = Config::builder()
+        .add_source(File::from_str("name \"ok\"\nratio 1.\n", FileFormat::Kdl))
+        .build();
+
+    assert_data_eq!(
+        res.unwrap_err().to_string(),
+        str!["Non-digit character found after the '.' of a float at line 2 column 7"]
+    );
+}
+
+#[test]
+fn test_error_conflicts() {
+    let parse = |text: &str| {
+        Config::builder()
+            .add_source(File::from_str(text, FileFormat::Kdl))
+            .build()
+            .unwrap_err()
+            .to_string()
+    };
+
+    assert_data_eq!(
+        parse("server \"main\" args=\"other\"\n"),
+        str!["`args` property or child is beside arguments in node `server` at line 1 column 1"]
+    );
+    assert_data_eq!(
+        parse("server port=8080 {\n    port 9090\n}\n"),
+        str!["child `port` has the name of a property in node `server` at line 1 column 1"]
+    );
+}
+
+#[test]
+fn test_render() {
+    let mut server = Map::new();
+    server.insert("args".to_owned(), Value::from(vec![Value::from("main")]));
+    server.insert("port".to_owned(), Value::from(8080));
+    server.insert(
+        "alias".to_owned(),
+        Value::from(vec![Value::from("www"), Value::from("api")]),
+    );
+    let mut user = Map::new();
+    user.insert("name".to_owned(), Value::from("alice"));
+    let mut values = Map::new();
+    values.insert("server".to_owned(), Value::from(server));
+    values.insert(
+        "user".to_owned(),
+        Value::from(vec![Value::from(user.clone()), Value::from(user)]),
+    );
+    values.insert("debug".to_owned(), Value::from(true));
+
+    let text = FileFormat::Kdl.render(&values).unwrap();
+    assert_data_eq!(
+        text.clone(),
+        str![[r#"
+debug #true
+server main {
+    alias www api
+    port 8080
+}
+user {
+    name alice
+}
+user {
+    name alice
+}
+
+"#]]
+    );
+
+    let c = Config::builder()
+        .add_source(File::from_str(&text, FileFormat::Kdl))
+        .build()
+        .unwrap();
+    assert_eq!(c.get_string("server.args[0]").unwrap(), "main");
+    assert_eq!(c.get_int("server.port").unwrap(), 8080);
+    assert_eq!(c.get_string("server.alias[1]").unwrap(), "api");
+    assert_eq!(c.get_string("user[1].name").unwrap(), "alice");
+    assert!(c.get_bool("debug").unwrap());
+}
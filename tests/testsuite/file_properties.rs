@@ -0,0 +1,137 @@
+#![cfg(feature = "properties")]
+
+use snapbox::{assert_data_eq, str};
+
+use config::{Config, File, FileFormat, FormatWriter, Map, Value};
+
+#[test]
+fn test_file() {
+    let c = Config::builder()
+        .add_source(File::new(
+            "tests/testsuite/file-properties",
+            FileFormat::Properties,
+        ))
+        .build()
+        .unwrap();
+
+    assert_eq!(c.get_int("server.port").unwrap(), 8080);
+    assert_eq!(c.get_string("server.host").unwrap(), "localhost");
+    assert_eq!(c.get_string("server.name").unwrap(), "Billing Service");
+    assert_eq!(c.get_string("greeting").unwrap(), "café 😀");
+    assert_eq!(c.get_string("path").unwrap(), r"C:\data\billing");
+    assert_eq!(
+        c.get_string("description").unwrap(),
+        "A long description spanning two lines"
+    );
+    assert_eq!(
+        c.get_table("names").unwrap()["key with spaces"]
+            .clone()
+            .into_string()
+            .unwrap(),
+        "spaced"
+    );
+    assert_eq!(
+        c.get::<Vec<String>>("list").unwrap(),
+        ["first".to_owned(), "second".to_owned()]
+    );
+    assert_eq!(c.get_string("empty").unwrap(), "");
+}
+
+#[test]
+fn test_location() {
+    let c = Config::builder()
+        .add_source(File::new(
+            "tests/testsuite/file-properties",
+            FileFormat::Properties,
+        ))
+        .track_layers(true)
+        .build()
+        .unwrap();
+
+    let location = c
+        .explain("server.host")
+        .unwrap()
+        .value()
+        .location()
+        .unwrap();
+    assert_eq!((location.line(), location.column()), (4, 15));
+}
+
+#[test]
+fn test_error_parse() {
+    let res = Config::builder()
+        .add_source(File::from_str("name=\\u00zz\n", FileFormat::Properties))
+        .build();
+
+    assert_data_eq!(
+        res.unwrap_err().to_string(),
+        str!["expected 4 hexadecimal digits in `/u` escape at line 1 column 10"]
+    );
+}
+
+#[test]
+fn test_error_key_conflict() {
+    let res = Config::builder()
+        .add_source(File::from_str(
+            "logging.level=INFO\nlogging.level.org.foo=DEBUG\n",
+            FileFormat::Properties,
+        ))
+        .build();
+
+    assert_data_eq!(
+        res.unwrap_err().to_string(),
+        str!["key `logging.level.org.foo` conflicts with the value at `logging.level` at line 2 column 1"]
+    );
+
+    // In the reverse order, the value would drop the table
+    let res = Config::builder()
+        .add_source(File::from_str(
+            "logging.level.org.foo=DEBUG\n  logging.level=INFO\n",
+            FileFormat::Properties,
+        ))
+        .build();
+
+    assert_data_eq!(
+        res.unwrap_err().to_string(),
+        str!["key `logging.level` conflicts with the keys under it at line 2 column 3"]
+    );
+
+    // Repeated keys and array items do not conflict
+    let c = Config::builder()
+        .add_source(File::from_str(
+            "level=INFO\nlevel=DEBUG\nlist[0]=a\nlist[1]=b\n",
+            FileFormat::Properties,
+        ))
+        .build()
+        .unwrap();
+
+    assert_eq!(c.get_string("level").unwrap(), "DEBUG");
+    assert_eq!(c.get::<Vec<String>>("list").unwrap(), ["a", "b"]);
+}
+
+#[test]
+fn test_render() {
+    let mut server = Map::new();
+    server.insert("port".to_owned(), Value::from(8080));
+    server.insert("name".to_owned(), Value::from(" café = ok"));
+    let mut values = Map::new();
+    values.insert("server".to_owned(), Value::from(server));
+    values.insert(
+        "hosts".to_owned(),
+        Value::from(vec![Value::from("a"), Value::from("b")]),
+    );
+
+    let text = FileFormat::Properties.render(&values).unwrap();
+    assert_eq!(
+        text,
+        "hosts[0]=a\nhosts[1]=b\nserver.name=\\ caf\\u00E9 = ok\nserver.port=8080\n"
+    );
+
+    let c = Config::builder()
+        .add_source(File::from_str(&text, FileFormat::Properties))
+        .build()
+        .unwrap();
+    assert_eq!(c.get_string("server.name").unwrap(), " café = ok");
+    assert_eq!(c.get_int("server.port").unwrap(), 8080);
+    assert_eq!(c.get_string("hosts[1]").unwrap(), "b");
+}
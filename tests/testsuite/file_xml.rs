@@ -0,0 +1,235 @@
+#![cfg(feature = "xml")]
+
+use serde_derive::Deserialize;
+use snapbox::{assert_data_eq, str};
+
+use config::{Config, File, FileFormat, FormatWriter, Map, Value, XmlFormat};
+
+fn config() -> Config {
+    Config::builder()
+        .add_source(File::new("tests/testsuite/file-xml", FileFormat::Xml))
+        .track_layers(true)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_file() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Settings {
+        version: u32,
+        server: Server,
+        users: Users,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Server {
+        port: u16,
+        tls: bool,
+        host: String,
+        alias: Vec<String>,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Users {
+        user: Vec<User>,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct User {
+        name: String,
+        #[serde(default)]
+        admin: bool,
+    }
+
+    let s: Settings = config().try_deserialize().unwrap();
+    assert_eq!(
+        s,
+        Settings {
+            version: 2,
+            server: Server {
+                port: 8080,
+                tls: true,
+                host: "localhost".to_owned(),
+                alias: vec!["www".to_owned(), "api".to_owned()],
+            },
+            users: Users {
+                user: vec![
+                    User {
+                        name: "alice".to_owned(),
+                        admin: true,
+                    },
+                    User {
+                        name: "bob".to_owned(),
+                        admin: false,
+                    },
+                ],
+            },
+        }
+    );
+}
+
+#[test]
+fn test_text() {
+    let c = config();
+
+    assert_eq!(c.get_string("motd.lang").unwrap(), "en");
+    assert_eq!(c.get_string("motd.text").unwrap(), "Welcome & enjoy");
+    assert_eq!(c.get_string("script").unwrap(), "if (a < b) { run(); }");
+    assert_eq!(c.get_string("empty").unwrap(), "");
+}
+
+#[test]
+fn test_mapping() {
+    let c = Config::builder()
+        .add_source(File::new(
+            "tests/testsuite/file-xml",
+            XmlFormat::default()
+                .attribute_prefix("@")
+                .text_key("$value"),
+        ))
+        .build()
+        .unwrap();
+
+    let motd = c.get_table("motd").unwrap();
+    assert_eq!(motd["@lang"].clone().into_string().unwrap(), "en");
+    assert_eq!(
+        motd["$value"].clone().into_string().unwrap(),
+        "Welcome & enjoy"
+    );
+    assert!(c.get_table("server").unwrap().contains_key("@port"));
+}
+
+#[test]
+fn test_location() {
+    let location = config()
+        .explain("server.host")
+        .unwrap()
+        .value()
+        .location()
+        .unwrap();
+
+    assert_eq!((location.line(), location.column()), (5, 5));
+}
+
+#[test]
+fn test_error_parse() {
+    let res = Config::builder()
+        .add_source(File::from_str(
+            "<config>\n  <port>80</host>\n</config>",
+            FileFormat::Xml,
+        ))
+        .build();
+
+    assert_data_eq!(
+        res.unwrap_err().to_string(),
+        str![
+            "ill-formed document: expected `</port>`, but `</host>` was found at line 2 column 11"
+        ]
+    );
+
+    let res = Config::builder()
+        .add_source(File::from_str("<config>\n  <port>80", FileFormat::Xml))
+        .build();
+
+    assert_data_eq!(
+        res.unwrap_err().to_string(),
+        str!["unclosed element `port` at line 2 column 11"]
+    );
+}
+
+#[test]
+fn test_error_structure() {
+    let parse = |text: &str| {
+        Config::builder()
+            .add_source(File::from_str(text, FileFormat::Xml))
+            .build()
+            .unwrap_err()
+            .to_string()
+    };
+
+    assert_data_eq!(
+        parse("<config port=\"80\">\n  <port>8080</port>\n</config>"),
+        str!["attribute `port` has the key of a child element in element `config` at line 1 column 1"]
+    );
+    assert_data_eq!(
+        parse("<config>\n  <motd lang=\"en\">Welcome<text>Hi</text></motd>\n</config>"),
+        str!["text has the key `text` of an attribute or a child element in element `motd` at line 2 column 3"]
+    );
+    assert_data_eq!(
+        parse("<config/>\n<other/>"),
+        str!["`other` is a second root element at line 2 column 1"]
+    );
+    assert_data_eq!(
+        parse("<config>8080</config>"),
+        str!["the root element `config` holds text rather than a table at line 1 column 1"]
+    );
+
+    // Unless a prefix tells attributes apart
+    let c = Config::builder()
+        .add_source(File::from_str(
+            "<config port=\"80\"><port>8080</port></config>",
+            XmlFormat::default().attribute_prefix("@"),
+        ))
+        .build()
+        .unwrap();
+    assert_eq!(c.get_int("port").unwrap(), 8080);
+}
+
+#[test]
+fn test_render() {
+    let mut server = Map::new();
+    server.insert("port".to_owned(), Value::from(8080));
+    server.insert(
+        "alias".to_owned(),
+        Value::from(vec![Value::from("www"), Value::from("a&b")]),
+    );
+    let mut values = Map::new();
+    values.insert("server".to_owned(), Value::from(server));
+    values.insert("debug".to_owned(), Value::from(true));
+
+    let text = FileFormat::Xml.render(&values).unwrap();
+    assert_data_eq!(
+        text.clone(),
+        str![[r#"
+<config>
+  <debug>true</debug>
+  <server>
+    <alias>www</alias>
+    <alias>a&amp;b</alias>
+    <port>8080</port>
+  </server>
+</config>
+
+"#]]
+    );
+
+    let c = Config::builder()
+        .add_source(File::from_str(&text, FileFormat::Xml))
+        .build()
+        .unwrap();
+    assert_eq!(c.get_int("server.port").unwrap(), 8080);
+    assert_eq!(c.get_string("server.alias[1]").unwrap(), "a&b");
+}
+
+#[test]
+fn test_render_attributes() {
+    let mut motd = Map::new();
+    motd.insert("@lang".to_owned(), Value::from("en"));
+    motd.insert("$value".to_owned(), Value::from("Welcome"));
+    let mut values = Map::new();
+    values.insert("motd".to_owned(), Value::from(motd));
+
+    let format = XmlFormat::default()
+        .attribute_prefix("@")
+        .text_key("$value");
+    assert_data_eq!(
+        format.render(&values).unwrap(),
+        str![[r#"
+<config>
+  <motd lang="en">Welcome</motd>
+</config>
+
+"#]]
+    );
+}
@@ -112,6 +112,45 @@ error false
     );
 }
 
+#[test]
+fn test_error_duplicated_key() {
+    let res = Config::builder()
+        .add_source(File::from_str(
+            r#"
+ok: true
+ok: false
+"#,
+            FileFormat::Yaml,
+        ))
+        .build();
+
+    assert_data_eq!(
+        res.unwrap_err().to_string(),
+        str![[r#""ok": duplicated key in mapping at byte 14 line 3 column 5"#]]
+    );
+}
+
+#[test]
+fn test_anchors() {
+    let c = Config::builder()
+        .add_source(File::from_str(
+            r#"
+base: &base
+  port: 8080
+1: &one one
+server: *base
+aliases: [*one, *one]
+"#,
+            FileFormat::Yaml,
+        ))
+        .build()
+        .unwrap();
+
+    assert_eq!(c.get_int("server.port").unwrap(), 8080);
+    assert_eq!(c.get::<Vec<String>>("aliases").unwrap(), ["one", "one"]);
+    assert_eq!(c.get_string("1").unwrap(), "one");
+}
+
 #[test]
 fn test_yaml_parsing_key() {
     #[derive(Debug, Deserialize)]
@@ -0,0 +1,145 @@
+#![cfg(all(feature = "toml", feature = "json", feature = "yaml"))]
+
+use snapbox::{assert_data_eq, str};
+
+use config::{ArrayMerge, Config, File, FileFormat, Transform};
+
+fn build(name: &str) -> Result<Config, config::ConfigError> {
+    Config::builder()
+        .add_source(File::with_name(name).include_key("include"))
+        .track_layers(true)
+        .build()
+}
+
+#[test]
+#[cfg(feature = "glob")]
+fn test_include() {
+    let c = build("tests/testsuite/include/main").unwrap();
+
+    // The including file wins over the included ones, later includes over earlier ones
+    assert_eq!(c.get_int("server.port").unwrap(), 8080);
+    assert_eq!(c.get_string("server.host").unwrap(), "localhost");
+    assert_eq!(c.get_int("server.workers").unwrap(), 4);
+    assert_eq!(c.get_string("name").unwrap(), "base");
+    assert_eq!(c.get_string("services.auth.url").unwrap(), "http://auth");
+    assert_eq!(
+        c.get_string("services.billing.url").unwrap(),
+        "http://billing"
+    );
+    assert!(c.get_string("include").is_err());
+}
+
+#[test]
+#[cfg(feature = "glob")]
+fn test_include_origin() {
+    let c = build("tests/testsuite/include/main").unwrap();
+
+    let explanation = c.explain("server.workers").unwrap();
+    assert_eq!(
+        explanation.value().origin(),
+        Some("tests/testsuite/include/base.json")
+    );
+}
+
+#[test]
+fn test_include_disabled() {
+    let c = Config::builder()
+        .add_source(File::with_name("tests/testsuite/include/main"))
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        c.get::<Vec<String>>("include").unwrap(),
+        ["common.toml", "services/*.yaml"]
+    );
+    assert!(c.get_string("server.host").is_err());
+}
+
+#[test]
+fn test_include_cycle() {
+    let err = build("tests/testsuite/include/cycle-a").unwrap_err();
+
+    assert_data_eq!(
+        err.to_string(),
+        str!["include cycle: tests/testsuite/include/cycle-a.toml -> tests/testsuite/include/cycle-b.toml -> tests/testsuite/include/cycle-a.toml, included in tests/testsuite/include/cycle-b.toml, included in tests/testsuite/include/cycle-a.toml"]
+    );
+}
+
+#[test]
+fn test_include_error_chain() {
+    let err = build("tests/testsuite/include/broken-main").unwrap_err();
+
+    assert_data_eq!(
+        err.to_string(),
+        str!["expected `,` or `}` at line 2 column 14 in tests/testsuite/include/broken.json, included in tests/testsuite/include/broken.toml, included in tests/testsuite/include/broken-main.toml"]
+    );
+}
+
+#[test]
+fn test_include_missing() {
+    let err = Config::builder()
+        .add_source(
+            File::from_str(
+                r#"include = "tests/testsuite/include/missing.toml""#,
+                FileFormat::Toml,
+            )
+            .include_key("include"),
+        )
+        .build()
+        .unwrap_err();
+
+    assert_data_eq!(
+        err.to_string(),
+        str![[
+            r#"configuration file "tests/testsuite/include/missing.toml" not found, included from a string source"#
+        ]]
+    );
+}
+
+#[test]
+#[cfg(not(feature = "glob"))]
+fn test_include_glob_disabled() {
+    let err = build("tests/testsuite/include/main").unwrap_err();
+
+    assert_data_eq!(
+        err.to_string(),
+        str!["including tests/testsuite/include/services/*.yaml requires the `glob` feature in tests/testsuite/include/main.toml"]
+    );
+}
+
+#[test]
+fn test_include_layers() {
+    let c = Config::builder()
+        .add_source(File::from_str("debug = true", FileFormat::Toml))
+        .add_source(File::with_name("tests/testsuite/include/layers").include_key("include"))
+        .array_merge(ArrayMerge::Append)
+        .build()
+        .unwrap();
+
+    // Included files merge like sources, deleting keys of the sources before
+    assert_eq!(c.get::<Vec<String>>("tags").unwrap(), ["a", "b"]);
+    assert_eq!(c.get_string("server.host").unwrap(), "localhost");
+    assert!(c.get_bool("server.tls").is_err());
+    assert!(c.get_bool("debug").is_err());
+}
+
+#[test]
+fn test_include_layers_transformed() {
+    let c = Config::builder()
+        .add_source(
+            Transform::new(
+                File::with_name("tests/testsuite/include/layers").include_key("include"),
+            )
+            .rename("server", "backend")
+            .unwrap(),
+        )
+        .array_merge(ArrayMerge::Append)
+        .build()
+        .unwrap();
+
+    // Each included file is transformed and merged on its own
+    assert_eq!(c.get::<Vec<String>>("tags").unwrap(), ["a", "b"]);
+    assert_eq!(c.get_string("backend.host").unwrap(), "localhost");
+    assert!(c.get_bool("backend.tls").is_err());
+    assert!(c.get_string("server.host").is_err());
+}
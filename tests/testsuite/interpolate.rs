@@ -0,0 +1,162 @@
+use snapbox::{assert_data_eq, str};
+
+use config::{builder::DefaultState, Config, ConfigBuilder};
+
+fn builder() -> ConfigBuilder<DefaultState> {
+    Config::builder()
+        .set_default("db.host", "localhost")
+        .unwrap()
+        .set_default("db.port", 5432)
+        .unwrap()
+        .interpolate(true)
+}
+
+#[test]
+fn test_interpolate_keys() {
+    let c = builder()
+        .set_default("db.url", "postgres://${db.host}:${db.port}/app")
+        .unwrap()
+        .set_override("db.host", "db.internal")
+        .unwrap()
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        c.get_string("db.url").unwrap(),
+        "postgres://db.internal:5432/app"
+    );
+}
+
+#[test]
+fn test_interpolate_chained() {
+    let c = builder()
+        .set_default("db.url", "postgres://${db.address}")
+        .unwrap()
+        .set_default("db.address", "${db.host}:${db.port}")
+        .unwrap()
+        .build()
+        .unwrap();
+
+    assert_eq!(c.get_string("db.url").unwrap(), "postgres://localhost:5432");
+}
+
+#[test]
+fn test_interpolate_keeps_type() {
+    let c = builder()
+        .set_default("replica.port", "${db.port}")
+        .unwrap()
+        .set_default("replica.db", "${db}")
+        .unwrap()
+        .build()
+        .unwrap();
+
+    assert_eq!(c.get::<u16>("replica.port").unwrap(), 5432);
+    assert_eq!(c.get_string("replica.db.host").unwrap(), "localhost");
+}
+
+#[test]
+fn test_interpolate_env() {
+    temp_env::with_vars(
+        [
+            ("INTERPOLATE_USER", Some("admin")),
+            ("INTERPOLATE_UNSET", None),
+        ],
+        || {
+            let c = builder()
+                .set_default("user", "${env:INTERPOLATE_USER}")
+                .unwrap()
+                .set_default("name", "${env:INTERPOLATE_UNSET:-app}")
+                .unwrap()
+                .set_default("address", "${env:INTERPOLATE_UNSET:-${db.host}}")
+                .unwrap()
+                .build()
+                .unwrap();
+
+            assert_eq!(c.get_string("user").unwrap(), "admin");
+            assert_eq!(c.get_string("name").unwrap(), "app");
+            assert_eq!(c.get_string("address").unwrap(), "localhost");
+        },
+    );
+}
+
+#[test]
+fn test_interpolate_default() {
+    let c = builder()
+        .set_default("user", "${db.user:-postgres}")
+        .unwrap()
+        .build()
+        .unwrap();
+
+    assert_eq!(c.get_string("user").unwrap(), "postgres");
+}
+
+#[test]
+fn test_interpolate_escape() {
+    let c = builder()
+        .set_default("template", "$${db.host} is ${db.host}, $5 is $5")
+        .unwrap()
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        c.get_string("template").unwrap(),
+        "${db.host} is localhost, $5 is $5"
+    );
+}
+
+#[test]
+fn test_interpolate_disabled() {
+    let c = Config::builder()
+        .set_default("url", "${db.host}")
+        .unwrap()
+        .build()
+        .unwrap();
+
+    assert_eq!(c.get_string("url").unwrap(), "${db.host}");
+}
+
+#[test]
+fn test_interpolate_cycle() {
+    let res = builder().set_default("a", "x${a}").unwrap().build();
+
+    assert_data_eq!(
+        res.unwrap_err().to_string(),
+        str!["interpolation cycle for key `a`: a -> a"]
+    );
+
+    let res = builder()
+        .set_default("a", "${b.c}")
+        .unwrap()
+        .set_default("b.c", "${a}")
+        .unwrap()
+        .build();
+
+    let err = res.unwrap_err().to_string();
+    assert!(err.starts_with("interpolation cycle"), "{}", err);
+}
+
+#[test]
+fn test_interpolate_not_set() {
+    let res = builder()
+        .set_default("url", "postgres://${db.hots}")
+        .unwrap()
+        .build();
+
+    assert_data_eq!(
+        res.unwrap_err().to_string(),
+        str!["key `url` interpolates `${db.hots}`, which is not set"]
+    );
+}
+
+#[test]
+fn test_interpolate_unclosed() {
+    let res = builder()
+        .set_default("url", "postgres://${db.host")
+        .unwrap()
+        .build();
+
+    assert_data_eq!(
+        res.unwrap_err().to_string(),
+        str!["unclosed `${` in the value of key `url`"]
+    );
+}
@@ -0,0 +1,142 @@
+use std::fs;
+use std::path::PathBuf;
+
+use config::{Config, KeyPerFile};
+
+/// A directory laid out like a Kubernetes volume: the files are symbolic links into `..data`.
+fn volume(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "config-key-per-file-{}-{}",
+        std::process::id(),
+        name
+    ));
+    let _ = fs::remove_dir_all(&path);
+    let data = path.join("..2024_01_01_00_00_00.000000000");
+    fs::create_dir_all(&data).unwrap();
+    for (name, content) in files {
+        fs::write(data.join(name), content).unwrap();
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::symlink;
+
+        symlink(data.file_name().unwrap(), path.join("..data")).unwrap();
+        for (name, _) in files {
+            symlink(PathBuf::from("..data").join(name), path.join(name)).unwrap();
+        }
+    }
+    #[cfg(not(unix))]
+    for (name, content) in files {
+        fs::write(path.join(name), content).unwrap();
+    }
+
+    path
+}
+
+#[test]
+fn test_key_per_file() {
+    let path = volume(
+        "plain",
+        &[
+            ("username", "admin\n"),
+            ("password", "hunter2\r\n"),
+            ("motd", "hello\n\n"),
+            ("port", "8080"),
+        ],
+    );
+    fs::write(path.join(".hidden"), "secret").unwrap();
+
+    let c = Config::builder()
+        .add_source(KeyPerFile::new(&path))
+        .build()
+        .unwrap();
+
+    assert_eq!(c.get_string("username").unwrap(), "admin");
+    assert_eq!(c.get_string("password").unwrap(), "hunter2");
+    // Only a single newline is trimmed
+    assert_eq!(c.get_string("motd").unwrap(), "hello\n");
+    assert_eq!(c.get_string("port").unwrap(), "8080");
+    assert!(c.get_string(".hidden").is_err());
+
+    // Neither the data directory nor its link are read as keys
+    let mut keys: Vec<String> = c
+        .try_deserialize::<config::Map<String, String>>()
+        .unwrap()
+        .into_keys()
+        .collect();
+    keys.sort();
+    assert_eq!(keys, ["motd", "password", "port", "username"]);
+}
+
+#[test]
+fn test_key_per_file_separator() {
+    let path = volume(
+        "separator",
+        &[("db__password", "hunter2"), ("db__pool__size", "4")],
+    );
+
+    let c = Config::builder()
+        .add_source(KeyPerFile::new(&path).separator("__"))
+        .build()
+        .unwrap();
+
+    assert_eq!(c.get_string("db.password").unwrap(), "hunter2");
+    assert_eq!(c.get_int("db.pool.size").unwrap(), 4);
+}
+
+#[test]
+fn test_key_per_file_try_parsing() {
+    let path = volume(
+        "parsing",
+        &[
+            ("debug", "true\n"),
+            ("port", "8080\n"),
+            ("ratio", "0.5"),
+            ("name", "app"),
+        ],
+    );
+
+    let c = Config::builder()
+        .add_source(KeyPerFile::new(&path).try_parsing(true))
+        .build()
+        .unwrap();
+
+    assert_eq!(c.get("debug").ok(), Some(true));
+    assert_eq!(c.get::<i64>("port").ok(), Some(8080));
+    assert_eq!(c.get::<f64>("ratio").ok(), Some(0.5));
+    assert_eq!(c.get_string("name").unwrap(), "app");
+}
+
+#[test]
+fn test_key_per_file_origin() {
+    let path = volume("origin", &[("token", "abc")]);
+
+    let c = Config::builder()
+        .add_source(KeyPerFile::new(&path))
+        .track_layers(true)
+        .build()
+        .unwrap();
+
+    let explanation = c.explain("token").unwrap();
+    assert_eq!(
+        explanation.winner().unwrap().origin(),
+        Some(path.join("token").to_string_lossy().as_ref())
+    );
+}
+
+#[test]
+fn test_key_per_file_missing_directory() {
+    let path = std::env::temp_dir().join("config-key-per-file-missing");
+
+    assert!(Config::builder()
+        .add_source(KeyPerFile::new(&path))
+        .build()
+        .is_err());
+
+    let c = Config::builder()
+        .add_source(KeyPerFile::new(&path).required(false))
+        .build()
+        .unwrap();
+    assert!(c.get_string("token").is_err());
+}
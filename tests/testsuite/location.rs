@@ -0,0 +1,139 @@
+use snapbox::{assert_data_eq, str};
+
+use config::{Config, File, FileFormat};
+
+fn location(c: &Config, key: &str) -> (usize, usize) {
+    let explanation = c.explain(key).unwrap();
+    let location = explanation.value().location().unwrap();
+    (location.line(), location.column())
+}
+
+fn build(text: &str, format: FileFormat) -> Config {
+    Config::builder()
+        .add_source(File::from_str(text, format))
+        .track_layers(true)
+        .build()
+        .unwrap()
+}
+
+#[test]
+#[cfg(feature = "toml")]
+fn test_location_toml() {
+    let c = build(
+        r#"
+debug = true
+dates = [1979-05-27 07:32:00, "two"]
+"quoted" = { inner.deep = 'x' }
+text = """
+multi
+line"""
+
+[place]
+name = "Torre di Pisa"
+
+[[place.reviews]]
+stars = 5
+
+[[place.reviews]]
+stars = 4
+"#,
+        FileFormat::Toml,
+    );
+
+    assert_eq!(location(&c, "debug"), (2, 9));
+    assert_eq!(location(&c, "dates[0]"), (3, 10));
+    assert_eq!(location(&c, "dates[1]"), (3, 31));
+    assert_eq!(location(&c, "quoted.inner.deep"), (4, 27));
+    assert_eq!(location(&c, "text"), (5, 8));
+    assert_eq!(location(&c, "place.name"), (10, 8));
+    assert_eq!(location(&c, "place.reviews[1]"), (15, 1));
+    assert_eq!(location(&c, "place.reviews[1].stars"), (16, 9));
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_location_json() {
+    let c = build(
+        r#"{
+  "debug": true,
+  "tags": ["a", "b\"c"],
+  "place": {
+    "name": "Torre di Pisa"
+  }
+}"#,
+        FileFormat::Json,
+    );
+
+    assert_eq!(location(&c, "debug"), (2, 12));
+    assert_eq!(location(&c, "tags[1]"), (3, 17));
+    assert_eq!(location(&c, "place.name"), (5, 13));
+}
+
+#[test]
+#[cfg(feature = "json5")]
+fn test_location_json5() {
+    let c = build(
+        r#"{
+  // A comment
+  debug: true,
+  'tags': ['a', /* b */ 'b',],
+  place: { name: "Torre di Pisa" },
+}"#,
+        FileFormat::Json5,
+    );
+
+    assert_eq!(location(&c, "debug"), (3, 10));
+    assert_eq!(location(&c, "tags[1]"), (4, 25));
+    assert_eq!(location(&c, "place.name"), (5, 18));
+}
+
+#[test]
+#[cfg(feature = "yaml")]
+fn test_location_yaml() {
+    let c = build(
+        r#"
+debug: true
+tags:
+  - a
+  - b
+place:
+  name: Torre di Pisa
+  1: one
+"#,
+        FileFormat::Yaml,
+    );
+
+    assert_eq!(location(&c, "debug"), (2, 8));
+    assert_eq!(location(&c, "tags[1]"), (5, 5));
+    assert_eq!(location(&c, "place.name"), (7, 9));
+    assert_eq!(location(&c, "place.1"), (8, 6));
+}
+
+#[test]
+#[cfg(feature = "toml")]
+fn test_location_span() {
+    let text = "name = \"app\"\n";
+    let c = build(text, FileFormat::Toml);
+
+    let explanation = c.explain("name").unwrap();
+    let span = explanation.value().location().unwrap().span().unwrap();
+    assert_eq!(&text[span], "\"app\"");
+}
+
+#[test]
+#[cfg(feature = "toml")]
+fn test_location_error() {
+    let c = Config::builder()
+        .add_source(File::with_name("tests/testsuite/location.toml"))
+        .build()
+        .unwrap();
+
+    let res = c.get::<u16>("database.port");
+
+    assert_data_eq!(
+        res.unwrap_err().to_string(),
+        str![[
+            r#"invalid type: string "not a port", expected an integer for key `database.port` in tests/testsuite/location.toml:6:8"#
+        ]]
+    );
+}
@@ -4,22 +4,44 @@ extern crate serde_derive;
 pub mod async_builder;
 pub mod case;
 pub mod defaults;
+pub mod delete;
+pub mod diagnostic;
+pub mod diff;
+pub mod directory;
 pub mod empty;
 pub mod env;
 pub mod errors;
+pub mod explain;
 pub mod file;
+pub mod file_dotenv;
+pub mod file_hcl;
 pub mod file_ini;
 pub mod file_json;
 pub mod file_json5;
+pub mod file_kdl;
+pub mod file_properties;
 pub mod file_ron;
 pub mod file_toml;
+pub mod file_xml;
 pub mod file_yaml;
 pub mod get;
+pub mod include;
 pub mod integer_range;
+pub mod interpolate;
+pub mod key_per_file;
+pub mod location;
 pub mod log;
 pub mod merge;
+pub mod mount;
+pub mod profile;
 pub mod ron_enum;
+pub mod serialize;
 pub mod set;
+pub mod suggest;
+pub mod transform;
 pub mod unsigned_int;
 pub mod unsigned_int_hm;
+pub mod unused;
+pub mod watch;
 pub mod weird_keys;
+pub mod write;
@@ -1,4 +1,4 @@
-use config::{Config, File, FileFormat, Map};
+use config::{ArrayMerge, Config, File, FileFormat, Map};
 
 #[test]
 #[cfg(feature = "json")]
@@ -80,3 +80,104 @@ fn test_merge_whole_config() {
     assert_eq!(config3.get("x").ok(), Some(10));
     assert_eq!(config3.get("y").ok(), Some(25));
 }
+
+#[cfg(feature = "json")]
+fn merge_arrays(builder: config::ConfigBuilder<config::builder::DefaultState>) -> Config {
+    builder
+        .add_source(File::from_str(
+            r#"{"list": [1, 2], "servers": [{"name": "a", "port": 80}, {"name": "b", "port": 81}]}"#,
+            FileFormat::Json,
+        ))
+        .add_source(File::from_str(
+            r#"{"list": [3], "servers": [{"name": "b", "port": 8081}, {"name": "c", "port": 82}]}"#,
+            FileFormat::Json,
+        ))
+        .build()
+        .unwrap()
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_merge_arrays_replace() {
+    let c = merge_arrays(Config::builder());
+
+    assert_eq!(c.get::<Vec<i32>>("list").unwrap(), vec![3]);
+    assert_eq!(c.get_array("servers").unwrap().len(), 2);
+    assert_eq!(c.get_string("servers[0].name").unwrap(), "b");
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_merge_arrays_append_prepend() {
+    let c = merge_arrays(Config::builder().array_merge(ArrayMerge::Append));
+    assert_eq!(c.get::<Vec<i32>>("list").unwrap(), vec![1, 2, 3]);
+    assert_eq!(c.get_array("servers").unwrap().len(), 4);
+
+    let c = merge_arrays(Config::builder().array_merge(ArrayMerge::Prepend));
+    assert_eq!(c.get::<Vec<i32>>("list").unwrap(), vec![3, 1, 2]);
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_merge_arrays_by_index() {
+    let c = merge_arrays(Config::builder().array_merge(ArrayMerge::ByIndex));
+
+    assert_eq!(c.get::<Vec<i32>>("list").unwrap(), vec![3, 2]);
+    // Tables at the same index merge key by key
+    assert_eq!(c.get_string("servers[0].name").unwrap(), "b");
+    assert_eq!(c.get_int("servers[0].port").unwrap(), 8081);
+    assert_eq!(c.get_string("servers[1].name").unwrap(), "c");
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_merge_arrays_by_key() {
+    let c = merge_arrays(
+        Config::builder()
+            .array_merge(ArrayMerge::Append)
+            .array_merge_at("servers", ArrayMerge::ByKey("name".into())),
+    );
+
+    assert_eq!(c.get::<Vec<i32>>("list").unwrap(), vec![1, 2, 3]);
+    let ports: Vec<(String, i64)> = c
+        .get_array("servers")
+        .unwrap()
+        .into_iter()
+        .map(|server| {
+            let server = server.into_table().unwrap();
+            (
+                server["name"].clone().into_string().unwrap(),
+                server["port"].clone().into_int().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        ports,
+        vec![
+            ("a".to_owned(), 80),
+            ("b".to_owned(), 8081),
+            ("c".to_owned(), 82)
+        ]
+    );
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_merge_arrays_overrides() {
+    let builder = Config::builder()
+        .array_merge(ArrayMerge::Append)
+        .set_default("list", vec![1])
+        .unwrap()
+        .add_source(File::from_str(r#"{"list": [2, 3]}"#, FileFormat::Json));
+
+    // Sources append to the defaults, overrides replace the result
+    let c = builder.build_cloned().unwrap();
+    assert_eq!(c.get::<Vec<i32>>("list").unwrap(), vec![1, 2, 3]);
+
+    let c = builder
+        .set_override("list", vec![4])
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(c.get::<Vec<i32>>("list").unwrap(), vec![4]);
+}
@@ -0,0 +1,52 @@
+#![cfg(feature = "json")]
+
+use config::{Config, Environment, File, FileFormat};
+
+#[test]
+fn test_source_at() {
+    let c = Config::builder()
+        .set_default("database.port", 5432)
+        .unwrap()
+        .add_source_at(
+            "database",
+            File::from_str(
+                r#"{"host": "localhost", "pool": {"size": 4}}"#,
+                FileFormat::Json,
+            ),
+        )
+        .unwrap()
+        .add_source_at(
+            "services[0].auth",
+            File::from_str(r#"{"issuer": "me"}"#, FileFormat::Json),
+        )
+        .unwrap()
+        .build()
+        .unwrap();
+
+    assert_eq!(c.get_string("database.host").unwrap(), "localhost");
+    assert_eq!(c.get_int("database.pool.size").unwrap(), 4);
+    // The source merges into what is already under the prefix
+    assert_eq!(c.get_int("database.port").unwrap(), 5432);
+    assert_eq!(c.get_string("services[0].auth.issuer").unwrap(), "me");
+    assert!(c.get_string("host").is_err());
+}
+
+#[test]
+fn test_source_at_environment() {
+    temp_env::with_vars([("MOUNT_PASSWORD", Some("hunter2"))], || {
+        let c = Config::builder()
+            .add_source_at("secrets", Environment::with_prefix("MOUNT"))
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert_eq!(c.get_string("secrets.password").unwrap(), "hunter2");
+    });
+}
+
+#[test]
+fn test_source_at_invalid_prefix() {
+    let res = Config::builder().add_source_at("database[", File::from_str("{}", FileFormat::Json));
+
+    assert!(res.is_err());
+}
@@ -0,0 +1,72 @@
+#![cfg(feature = "toml")]
+
+use config::{Config, ConfigBuilder, File};
+
+fn builder() -> ConfigBuilder<config::builder::DefaultState> {
+    Config::builder()
+        .add_source(File::with_name("tests/testsuite/profile/Settings"))
+        .add_source(File::with_name("tests/testsuite/profile/Local"))
+}
+
+#[test]
+fn test_profile() {
+    let c = builder().profile("prod").build().unwrap();
+
+    assert_eq!(c.get_string("name").unwrap(), "app");
+    assert_eq!(c.get_int("server.port").unwrap(), 80);
+    // Later sources win, whichever section the value comes from
+    assert_eq!(c.get_string("log.level").unwrap(), "warn");
+    assert!(c.get_table("profile").is_err());
+    assert!(c.get_table("default").is_err());
+
+    let c = builder().profile("dev").build().unwrap();
+    assert_eq!(c.get_int("server.port").unwrap(), 8080);
+    assert_eq!(c.get_string("log.level").unwrap(), "debug");
+}
+
+#[test]
+fn test_profile_env() {
+    temp_env::with_var("PROFILE_TEST_PROFILE", Some("dev"), || {
+        let c = builder()
+            .profile("prod")
+            .profile_env("PROFILE_TEST_PROFILE")
+            .build()
+            .unwrap();
+
+        assert_eq!(c.get_string("log.level").unwrap(), "debug");
+    });
+
+    temp_env::with_var_unset("PROFILE_TEST_PROFILE", || {
+        let c = builder()
+            .profile_env("PROFILE_TEST_PROFILE")
+            .build()
+            .unwrap();
+
+        // Without a profile, only the defaults apply
+        assert_eq!(c.get_int("server.port").unwrap(), 8080);
+        assert_eq!(c.get_string("log.level").unwrap(), "info");
+    });
+}
+
+#[test]
+fn test_profile_disabled() {
+    let c = builder().build().unwrap();
+
+    assert_eq!(c.get_int("default.server.port").unwrap(), 8080);
+    assert_eq!(c.get_string("profile.prod.log.level").unwrap(), "warn");
+}
+
+#[test]
+fn test_profile_explain() {
+    let c = builder()
+        .profile("prod")
+        .track_layers(true)
+        .build()
+        .unwrap();
+
+    let explanation = c.explain("server.port").unwrap();
+    assert_eq!(
+        explanation.winner().unwrap().origin(),
+        Some("tests/testsuite/profile/Settings.toml")
+    );
+}
@@ -0,0 +1,269 @@
+#![cfg(feature = "json")]
+
+use snapbox::{assert_data_eq, str};
+
+use config::{Config, File, FileFormat, FormatWriter};
+
+#[test]
+fn test_serialize_json() {
+    let c = Config::builder()
+        .add_source(File::from_str(
+            r#"
+{
+  "debug": true,
+  "port": 8080,
+  "ratio": 0.5,
+  "name": "app",
+  "nothing": null,
+  "tags": ["a", "b"],
+  "place": {
+    "name": "Torre di Pisa",
+    "reviews": [1, 2, 3]
+  }
+}
+"#,
+            FileFormat::Json,
+        ))
+        .build()
+        .unwrap();
+
+    let text = serde_json::to_string(&c).unwrap();
+    let reparsed = Config::builder()
+        .add_source(File::from_str(&text, FileFormat::Json))
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        serde_json::to_value(&c).unwrap(),
+        serde_json::to_value(&reparsed).unwrap()
+    );
+}
+
+#[test]
+#[cfg(feature = "toml")]
+fn test_serialize_toml() {
+    let c = Config::builder()
+        .add_source(File::from_str(
+            r#"
+debug = true
+port = 8080
+ratio = 0.5
+tags = ["a", "b"]
+
+[place]
+name = "Torre di Pisa"
+reviews = [1, 2, 3]
+"#,
+            FileFormat::Toml,
+        ))
+        .build()
+        .unwrap();
+
+    let text = toml::to_string(&c).unwrap();
+    let reparsed = Config::builder()
+        .add_source(File::from_str(&text, FileFormat::Toml))
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        serde_json::to_value(&c).unwrap(),
+        serde_json::to_value(&reparsed).unwrap()
+    );
+}
+
+#[test]
+#[cfg(feature = "yaml")]
+fn test_serialize_yaml() {
+    let c = Config::builder()
+        .add_source(File::from_str(
+            r#"
+debug: true
+port: 8080
+ratio: 0.5
+nothing: ~
+tags:
+  - a
+  - b
+place:
+  name: Torre di Pisa
+  reviews: [1, 2, 3]
+"#,
+            FileFormat::Yaml,
+        ))
+        .build()
+        .unwrap();
+
+    // There is no serde serializer for YAML among the dependencies, the format renders the text
+    let text = FileFormat::Yaml
+        .render(&c.cache.clone().into_table().unwrap())
+        .unwrap();
+    let reparsed = Config::builder()
+        .add_source(File::from_str(&text, FileFormat::Yaml))
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        serde_json::to_value(&c).unwrap(),
+        serde_json::to_value(&reparsed).unwrap()
+    );
+}
+
+#[test]
+#[cfg(feature = "ini")]
+fn test_serialize_ini() {
+    let c = Config::builder()
+        .add_source(File::from_str(
+            r#"
+debug = true
+port = 8080
+
+[place]
+name = Torre di Pisa
+"#,
+            FileFormat::Ini,
+        ))
+        .build()
+        .unwrap();
+
+    // There is no serde serializer for INI among the dependencies, the format renders the text
+    let text = FileFormat::Ini
+        .render(&c.cache.clone().into_table().unwrap())
+        .unwrap();
+    let reparsed = Config::builder()
+        .add_source(File::from_str(&text, FileFormat::Ini))
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        serde_json::to_value(&c).unwrap(),
+        serde_json::to_value(&reparsed).unwrap()
+    );
+}
+
+#[test]
+#[cfg(feature = "ron")]
+fn test_serialize_ron() {
+    let c = Config::builder()
+        .add_source(File::from_str(
+            r#"
+(
+  debug: true,
+  port: 8080,
+  ratio: 0.5,
+  nothing: None,
+  tags: ["a", "b"],
+  place: (
+    name: "Torre di Pisa",
+    reviews: [1, 2, 3],
+  ),
+)
+"#,
+            FileFormat::Ron,
+        ))
+        .build()
+        .unwrap();
+
+    let text = ron::to_string(&c).unwrap();
+    let reparsed = Config::builder()
+        .add_source(File::from_str(&text, FileFormat::Ron))
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        serde_json::to_value(&c).unwrap(),
+        serde_json::to_value(&reparsed).unwrap()
+    );
+}
+
+#[test]
+#[cfg(feature = "json5")]
+fn test_serialize_json5() {
+    let c = Config::builder()
+        .add_source(File::from_str(
+            r#"
+{
+  debug: true,
+  port: 8080,
+  ratio: 0.5,
+  nothing: null,
+  tags: ['a', 'b'],
+  place: {
+    name: 'Torre di Pisa',
+    reviews: [1, 2, 3,],
+  },
+}
+"#,
+            FileFormat::Json5,
+        ))
+        .build()
+        .unwrap();
+
+    let text = json5_rs::to_string(&c).unwrap();
+    let reparsed = Config::builder()
+        .add_source(File::from_str(&text, FileFormat::Json5))
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        serde_json::to_value(&c).unwrap(),
+        serde_json::to_value(&reparsed).unwrap()
+    );
+}
+
+#[test]
+fn test_serialize_integer_widths() {
+    let c = Config::builder()
+        .set_default("i64", i64::MIN)
+        .unwrap()
+        .set_default("i128", i128::MIN)
+        .unwrap()
+        .set_default("u64", u64::MAX)
+        .unwrap()
+        .set_default("u128", u128::MAX)
+        .unwrap()
+        .set_default("nil", None::<i64>)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let table = c.cache.into_table().unwrap();
+    let to_json = |key: &str| serde_json::to_string(&table[key]).unwrap();
+
+    assert_data_eq!(to_json("i64"), str!["-9223372036854775808"]);
+    assert_data_eq!(
+        to_json("i128"),
+        str!["-170141183460469231731687303715884105728"]
+    );
+    assert_data_eq!(to_json("u64"), str!["18446744073709551615"]);
+    assert_data_eq!(
+        to_json("u128"),
+        str!["340282366920938463463374607431768211455"]
+    );
+    assert_data_eq!(to_json("nil"), str!["null"]);
+}
+
+#[test]
+#[cfg(feature = "preserve_order")]
+fn test_serialize_preserve_order() {
+    let c = Config::builder()
+        .add_source(File::from_str(
+            r#"
+{
+  "zebra": 1,
+  "apple": 2,
+  "mango": {
+    "z": 1,
+    "a": 2
+  }
+}
+"#,
+            FileFormat::Json,
+        ))
+        .build()
+        .unwrap();
+
+    assert_data_eq!(
+        serde_json::to_string(&c).unwrap(),
+        str![[r#"{"zebra":1,"apple":2,"mango":{"z":1,"a":2}}"#]]
+    );
+}
@@ -0,0 +1,104 @@
+#![cfg(feature = "json")]
+
+use serde_derive::Deserialize;
+use snapbox::{assert_data_eq, str};
+
+use config::{Config, ConfigError, File, FileFormat};
+
+fn config(json: &str) -> Config {
+    Config::builder()
+        .add_source(File::from_str(json, FileFormat::Json))
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_suggest_not_found() {
+    let c = config(r#"{"database": {"url": "postgres://localhost", "user": "app"}}"#);
+
+    let err = c.get_string("databse.url").unwrap_err();
+
+    assert_data_eq!(
+        err.to_string(),
+        str![[r#"configuration property "databse.url" not found, did you mean "database.url"?"#]]
+    );
+    match err {
+        ConfigError::NotFound(ref key, ref suggestions) => {
+            assert_eq!(key, "databse.url");
+            assert_eq!(suggestions, &c.suggest_keys(key));
+            assert_eq!(suggestions, &["database.url"]);
+        }
+        _ => panic!("Wrong error: {:?}", err),
+    }
+}
+
+#[test]
+fn test_suggest_not_found_none() {
+    let c = config(r#"{"database": {"url": "postgres://localhost"}}"#);
+
+    assert!(c.suggest_keys("name").is_empty());
+    assert_data_eq!(
+        c.get_string("name").unwrap_err().to_string(),
+        str![[r#"configuration property "name" not found"#]]
+    );
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct Database {
+    url: String,
+    port: u16,
+}
+
+#[test]
+fn test_suggest_missing_field() {
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Settings {
+        database: Database,
+    }
+
+    let c = config(r#"{"database": {"url": "postgres://localhost", "prot": 5432}}"#);
+
+    assert_data_eq!(
+        c.clone()
+            .try_deserialize::<Settings>()
+            .unwrap_err()
+            .to_string(),
+        str!["missing field `port` for key `database`, did you mean `database.prot`?"]
+    );
+    assert_data_eq!(
+        c.get::<Database>("database").unwrap_err().to_string(),
+        str!["missing field `port` for key `database`, did you mean `database.prot`?"]
+    );
+}
+
+#[test]
+fn test_suggest_unknown_field() {
+    #[derive(Debug, Deserialize)]
+    #[serde(deny_unknown_fields)]
+    #[allow(dead_code)]
+    struct Logging {
+        level: String,
+        #[serde(default)]
+        verbose: bool,
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Settings {
+        logging: Logging,
+    }
+
+    let c = config(r#"{"logging": {"level": "info", "verbos": true}}"#);
+    assert_data_eq!(
+        c.try_deserialize::<Settings>().unwrap_err().to_string(),
+        str!["unknown field `verbos` for key `logging`, did you mean `logging.verbose`?"]
+    );
+
+    let c = config(r#"{"logging": {"level": "info", "color": true}}"#);
+    assert_data_eq!(
+        c.try_deserialize::<Settings>().unwrap_err().to_string(),
+        str!["unknown field `color` for key `logging`, expected one of `level`, `verbose`"]
+    );
+}
@@ -0,0 +1,184 @@
+#![cfg(feature = "json")]
+
+use config::{Config, Environment, File, FileFormat, Transform, ValueKind};
+
+fn source() -> File<config::FileSourceString, FileFormat> {
+    File::from_str(
+        r#"{
+            "database": {"host": "localhost", "password": "hunter2", "pool": {"size": 4}},
+            "cache": {"password": "secret", "ttl": 60},
+            "servers": [{"port": 80}, {"port": 443}],
+            "debug": true
+        }"#,
+        FileFormat::Json,
+    )
+}
+
+#[test]
+fn test_keep() {
+    let c = Config::builder()
+        .add_source(
+            Transform::new(source())
+                .keep("database.pool")
+                .unwrap()
+                .keep("cache.*")
+                .unwrap(),
+        )
+        .build()
+        .unwrap();
+
+    assert_eq!(c.get_int("database.pool.size").unwrap(), 4);
+    assert_eq!(c.get_int("cache.ttl").unwrap(), 60);
+    assert!(c.get_string("database.host").is_err());
+    assert!(c.get_bool("debug").is_err());
+    assert!(c.get_array("servers").is_err());
+}
+
+#[test]
+fn test_exclude() {
+    let c = Config::builder()
+        .add_source(Transform::new(source()).exclude("**.password").unwrap())
+        .build()
+        .unwrap();
+
+    assert!(c.get_string("database.password").is_err());
+    assert!(c.get_string("cache.password").is_err());
+    assert_eq!(c.get_string("database.host").unwrap(), "localhost");
+    assert!(c.get_bool("debug").unwrap());
+}
+
+#[test]
+fn test_exclude_wildcards() {
+    let c = Config::builder()
+        .add_source(
+            Transform::new(source())
+                .exclude("database.[!h]?ss*")
+                .unwrap()
+                .exclude("[a-c]ache.t?l")
+                .unwrap(),
+        )
+        .build()
+        .unwrap();
+
+    assert!(c.get_string("database.password").is_err());
+    assert!(c.get_int("cache.ttl").is_err());
+    assert_eq!(c.get_string("database.host").unwrap(), "localhost");
+    assert_eq!(c.get_string("cache.password").unwrap(), "secret");
+}
+
+#[test]
+fn test_invalid_pattern() {
+    let res = Transform::new(source()).keep("database.[");
+
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_rename() {
+    let c = Config::builder()
+        .add_source(
+            Transform::new(source())
+                .rename("database", "db")
+                .unwrap()
+                .rename("cache.ttl", "limits.cache_ttl")
+                .unwrap()
+                .rename("missing", "elsewhere")
+                .unwrap(),
+        )
+        .build()
+        .unwrap();
+
+    assert_eq!(c.get_string("db.host").unwrap(), "localhost");
+    assert!(c.get_string("database.host").is_err());
+    assert_eq!(c.get_int("limits.cache_ttl").unwrap(), 60);
+    assert!(c.get_int("cache.ttl").is_err());
+    assert!(c.get_string("elsewhere").is_err());
+}
+
+#[test]
+fn test_map_values() {
+    let c = Config::builder()
+        .add_source(Transform::new(source()).map_values(|key, mut value| {
+            match value.kind {
+                ValueKind::I64(port) if key.starts_with("servers[") => {
+                    value.kind = ValueKind::I64(port + 8000);
+                }
+                _ if key.ends_with("password") => value.kind = ValueKind::String("***".to_owned()),
+                _ => {}
+            }
+            value
+        }))
+        .build()
+        .unwrap();
+
+    assert_eq!(c.get_int("servers[0].port").unwrap(), 8080);
+    assert_eq!(c.get_int("servers[1].port").unwrap(), 8443);
+    assert_eq!(c.get_string("database.password").unwrap(), "***");
+    assert_eq!(c.get_string("cache.password").unwrap(), "***");
+    assert_eq!(c.get_int("cache.ttl").unwrap(), 60);
+}
+
+#[test]
+fn test_lowercase_keys() {
+    let c = Config::builder()
+        .add_source(
+            Transform::new(File::from_str(
+                r#"{"Server": {"Port": 8080, "Hosts": [{"Name": "a"}]}}"#,
+                FileFormat::Json,
+            ))
+            .lowercase_keys(),
+        )
+        .build()
+        .unwrap();
+
+    assert_eq!(c.get_int("server.port").unwrap(), 8080);
+    assert_eq!(c.get_string("server.hosts[0].name").unwrap(), "a");
+}
+
+#[cfg(feature = "convert-case")]
+#[test]
+fn test_convert_case() {
+    use config::Case;
+
+    let c = Config::builder()
+        .add_source(
+            Transform::new(File::from_str(
+                r#"{"serverSettings": {"maxConnections": 10}}"#,
+                FileFormat::Json,
+            ))
+            .convert_case(Case::Snake),
+        )
+        .build()
+        .unwrap();
+
+    assert_eq!(c.get_int("server_settings.max_connections").unwrap(), 10);
+}
+
+#[test]
+fn test_transform_environment() {
+    temp_env::with_vars(
+        [
+            ("TRANSFORM_DB_HOST", Some("localhost")),
+            ("TRANSFORM_DB_PASSWORD", Some("hunter2")),
+            ("TRANSFORM_LOG", Some("debug")),
+        ],
+        || {
+            let c = Config::builder()
+                .add_source(
+                    Transform::new(Environment::with_prefix("TRANSFORM").separator("_"))
+                        .keep("db.*")
+                        .unwrap()
+                        .exclude("db.password")
+                        .unwrap()
+                        .rename("db", "database")
+                        .unwrap(),
+                )
+                .build()
+                .unwrap();
+
+            assert_eq!(c.get_string("database.host").unwrap(), "localhost");
+            assert!(c.get_string("database.password").is_err());
+            assert!(c.get_string("log").is_err());
+        },
+    );
+}
@@ -0,0 +1,112 @@
+use serde_derive::Deserialize;
+use snapbox::{assert_data_eq, str};
+
+use config::{Config, Environment, File, FileFormat, Map};
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct Server {
+    host: String,
+    port: u16,
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct Settings {
+    server: Server,
+    #[serde(default)]
+    features: Map<String, bool>,
+}
+
+#[test]
+#[cfg(feature = "toml")]
+fn test_unused_keys() {
+    let mut env = Map::new();
+    env.insert("UNUSED__SERVER__TIMEOUT".to_owned(), "30".to_owned());
+
+    let (settings, unused) = Config::builder()
+        .add_source(File::from_str(
+            r#"
+[server]
+host = "localhost"
+port = 8080
+prot = 80
+
+[features]
+beta = true
+
+[logging]
+level = "debug"
+"#,
+            FileFormat::Toml,
+        ))
+        .add_source(
+            Environment::with_prefix("UNUSED")
+                .separator("__")
+                .source(Some(env)),
+        )
+        .build()
+        .unwrap()
+        .try_deserialize_reporting_unused::<Settings>()
+        .unwrap();
+
+    assert_eq!(settings.server.port, 8080);
+    assert!(settings.features["beta"]);
+
+    let keys: Vec<_> = unused.iter().map(|unused| unused.key()).collect();
+    assert_eq!(keys, ["logging", "server.prot", "server.timeout"]);
+    assert_eq!(unused[2].origin(), Some("the environment"));
+    assert_eq!(unused[1].value().location().unwrap().line(), 5);
+}
+
+#[test]
+#[cfg(feature = "toml")]
+fn test_unused_display() {
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Database {
+        url: String,
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Settings {
+        database: Database,
+    }
+
+    let (_, unused) = Config::builder()
+        .add_source(File::with_name("tests/testsuite/location.toml"))
+        .build()
+        .unwrap()
+        .try_deserialize_reporting_unused::<Settings>()
+        .unwrap();
+
+    let unused: Vec<_> = unused.iter().map(ToString::to_string).collect();
+    assert_data_eq!(
+        unused.join("\n"),
+        str![[r#"
+database.port in tests/testsuite/location.toml:6:8
+name in tests/testsuite/location.toml:2:8
+"#]]
+    );
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_unused_error() {
+    let c = Config::builder()
+        .add_source(File::from_str(
+            r#"{"server": {"host": "localhost", "port": "http"}, "extra": 1}"#,
+            FileFormat::Json,
+        ))
+        .build()
+        .unwrap();
+
+    assert_data_eq!(
+        c.clone()
+            .try_deserialize_reporting_unused::<Settings>()
+            .unwrap_err()
+            .to_string(),
+        c.try_deserialize::<Settings>().unwrap_err().to_string()
+    );
+}
@@ -0,0 +1,342 @@
+#![cfg(all(feature = "watch", feature = "toml"))]
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use config::{
+    Config, ConfigError, Directory, File, KeyPerFile, Map, ReloadableConfig, Source, Transform,
+    Value,
+};
+
+/// An empty directory of its own for each test.
+fn directory(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("config-watch-{}-{}", std::process::id(), name));
+    let _ = fs::remove_dir_all(&path);
+    fs::create_dir_all(&path).unwrap();
+    path
+}
+
+fn watch(builder: config::ConfigBuilder<config::builder::DefaultState>) -> ReloadableConfig {
+    ReloadableConfig::with_debounce(builder, Duration::from_millis(20)).unwrap()
+}
+
+/// Reloads only when asked to, as changes wait for an hour to settle.
+fn watch_slowly(builder: config::ConfigBuilder<config::builder::DefaultState>) -> ReloadableConfig {
+    ReloadableConfig::with_debounce(builder, Duration::from_secs(3600)).unwrap()
+}
+
+/// Waits for the watcher to pick up a change.
+fn wait_until(mut condition: impl FnMut() -> bool) {
+    let start = Instant::now();
+    while !condition() {
+        assert!(
+            start.elapsed() < Duration::from_secs(10),
+            "timed out waiting for a reload"
+        );
+        thread::sleep(Duration::from_millis(10));
+    }
+}
+
+#[test]
+fn test_watch_reload() {
+    let dir = directory("reload");
+    let path = dir.join("Settings.toml");
+    fs::write(&path, "port = 1").unwrap();
+
+    let settings = watch(Config::builder().add_source(File::from(path.as_path())));
+    let before = settings.snapshot();
+    assert_eq!(before.get_int("port").unwrap(), 1);
+
+    fs::write(&path, "port = 2").unwrap();
+    wait_until(|| settings.snapshot().get_int("port").ok() == Some(2));
+
+    // Snapshots taken before stay as they were
+    assert_eq!(before.get_int("port").unwrap(), 1);
+
+    drop(settings);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_watch_keep_last_good() {
+    let dir = directory("last-good");
+    let path = dir.join("Settings.toml");
+    fs::write(&path, "port = 1").unwrap();
+
+    let settings = watch(Config::builder().add_source(File::from(path.as_path())));
+
+    fs::write(&path, "port = ").unwrap();
+    wait_until(|| settings.last_error().is_some());
+    assert_eq!(settings.snapshot().get_int("port").unwrap(), 1);
+
+    fs::write(&path, "port = 3").unwrap();
+    wait_until(|| settings.snapshot().get_int("port").ok() == Some(3));
+    assert!(settings.last_error().is_none());
+
+    drop(settings);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_watch_missing_file() {
+    let dir = directory("missing");
+    let base = dir.join("Settings.toml");
+    fs::write(&base, "port = 1").unwrap();
+    let local = dir.join("local");
+
+    let settings = watch(
+        Config::builder()
+            .add_source(File::from(base.as_path()))
+            .add_source(File::from(local.as_path()).required(false)),
+    );
+    assert_eq!(settings.paths(), vec![base, local.clone()]);
+
+    // The optional file is picked up once it is created, with any of the formats
+    fs::write(local.with_extension("toml"), "port = 4").unwrap();
+    wait_until(|| settings.snapshot().get_int("port").ok() == Some(4));
+
+    drop(settings);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_watch_directory() {
+    let dir = directory("directory");
+    let conf = dir.join("conf.d");
+    fs::create_dir_all(&conf).unwrap();
+
+    let settings =
+        watch(Config::builder().add_source(Directory::new(conf.as_path()).recursive(true)));
+    assert!(settings.snapshot().get_int("port").is_err());
+
+    // Files added once it started are read
+    fs::write(conf.join("10-server.toml"), "port = 1").unwrap();
+    wait_until(|| settings.snapshot().get_int("port").ok() == Some(1));
+
+    // So are the subdirectories and their files
+    let sub = conf.join("sub");
+    fs::create_dir_all(&sub).unwrap();
+    wait_until(|| settings.paths().contains(&sub));
+    fs::write(sub.join("log.toml"), "level = \"debug\"").unwrap();
+    wait_until(|| settings.snapshot().get_string("level").ok().as_deref() == Some("debug"));
+
+    // Removed files are no longer read
+    fs::remove_file(conf.join("10-server.toml")).unwrap();
+    wait_until(|| settings.snapshot().get_int("port").is_err());
+
+    drop(settings);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+#[cfg(unix)]
+fn test_watch_key_per_file_swap() {
+    use std::os::unix::fs::symlink;
+
+    // Kubernetes updates a volume by pointing `..data` at a new directory of the same files
+    let dir = directory("key-per-file");
+    fs::create_dir_all(dir.join("..2024_01_01")).unwrap();
+    fs::write(dir.join("..2024_01_01").join("port"), "1").unwrap();
+    symlink("..2024_01_01", dir.join("..data")).unwrap();
+    symlink("..data/port", dir.join("port")).unwrap();
+
+    let settings =
+        watch(Config::builder().add_source(KeyPerFile::new(dir.as_path()).try_parsing(true)));
+    assert_eq!(settings.snapshot().get_int("port").unwrap(), 1);
+
+    fs::create_dir_all(dir.join("..2024_01_02")).unwrap();
+    fs::write(dir.join("..2024_01_02").join("port"), "2").unwrap();
+    symlink("..2024_01_02", dir.join("..data_tmp")).unwrap();
+    fs::rename(dir.join("..data_tmp"), dir.join("..data")).unwrap();
+    fs::remove_dir_all(dir.join("..2024_01_01")).unwrap();
+    wait_until(|| settings.snapshot().get_int("port").ok() == Some(2));
+
+    drop(settings);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+/// A source reading the value of `port` from the file at its path.
+#[derive(Clone, Debug)]
+struct PortFile(PathBuf);
+
+impl Source for PortFile {
+    fn clone_into_box(&self) -> Box<dyn Source + Send + Sync> {
+        Box::new(self.clone())
+    }
+
+    fn collect(&self) -> Result<Map<String, Value>, ConfigError> {
+        let text = fs::read_to_string(&self.0).map_err(|e| ConfigError::Foreign(Box::new(e)))?;
+        let port: i64 = text
+            .trim()
+            .parse()
+            .map_err(|_| ConfigError::Message(text))?;
+
+        Ok(Map::from([("port".to_owned(), port.into())]))
+    }
+
+    fn watch_paths(&self) -> Vec<PathBuf> {
+        vec![self.0.clone()]
+    }
+}
+
+#[test]
+fn test_watch_source_paths() {
+    let dir = directory("source-paths");
+    let path = dir.join("port");
+    fs::write(&path, "1").unwrap();
+
+    let settings = watch(Config::builder().add_source(PortFile(path.clone())));
+    assert_eq!(settings.paths(), vec![path.clone()]);
+
+    fs::write(&path, "6").unwrap();
+    wait_until(|| settings.snapshot().get_int("port").ok() == Some(6));
+
+    drop(settings);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_watch_grouped_sources() {
+    let dir = directory("grouped");
+    let base = dir.join("base.toml");
+    fs::write(&base, "port = 1").unwrap();
+    let local = dir.join("local.toml");
+    fs::write(&local, "debug = true").unwrap();
+
+    let sources: Vec<Box<dyn Source + Send + Sync>> = vec![
+        Box::new(File::from(base.as_path())),
+        Box::new(File::from(local.as_path())),
+    ];
+    let settings = watch(Config::builder().add_source(sources));
+    assert_eq!(settings.paths(), vec![base, local.clone()]);
+
+    fs::write(&local, "port = 7").unwrap();
+    wait_until(|| settings.snapshot().get_int("port").ok() == Some(7));
+
+    drop(settings);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_watch_transformed_source() {
+    let dir = directory("transformed");
+    let path = dir.join("Settings.toml");
+    fs::write(&path, "PORT = 1").unwrap();
+
+    let source = Transform::new(File::from(path.as_path())).lowercase_keys();
+    let settings = watch(Config::builder().add_source(source));
+    assert_eq!(settings.paths(), vec![path.clone()]);
+
+    fs::write(&path, "PORT = 8").unwrap();
+    wait_until(|| settings.snapshot().get_int("port").ok() == Some(8));
+
+    drop(settings);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_watch_reader() {
+    let dir = directory("reader");
+    let path = dir.join("Settings.toml");
+    fs::write(&path, "port = 1").unwrap();
+
+    let settings = watch_slowly(Config::builder().add_source(File::from(path.as_path())));
+    let mut reader = settings.reader();
+    assert_eq!(reader.current().get_int("port").unwrap(), 1);
+
+    fs::write(&path, "port = 5").unwrap();
+    settings.reload().unwrap();
+    assert_eq!(reader.current().get_int("port").unwrap(), 5);
+
+    // An explicit reload reports its error and keeps the configuration
+    fs::write(&path, "port = ").unwrap();
+    assert!(settings.reload().is_err());
+    assert_eq!(reader.current().get_int("port").unwrap(), 5);
+
+    drop(settings);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_watch_subscribe() {
+    let dir = directory("subscribe");
+    let path = dir.join("Settings.toml");
+    fs::write(&path, "port = 1\n[log]\nlevel = \"info\"").unwrap();
+
+    let settings = watch_slowly(Config::builder().add_source(File::from(path.as_path())));
+    let changes = Arc::new(Mutex::new(Vec::new()));
+    settings
+        .subscribe("log.level", {
+            let changes = Arc::clone(&changes);
+            move |change| {
+                changes.lock().unwrap().push((
+                    change.key().to_owned(),
+                    change.old_value().map(ToString::to_string),
+                    change.new_value().map(ToString::to_string),
+                ));
+            }
+        })
+        .unwrap();
+
+    // Unrelated edits leave the subscriber alone
+    fs::write(&path, "port = 2\n[log]\nlevel = \"info\"").unwrap();
+    settings.reload().unwrap();
+    assert!(changes.lock().unwrap().is_empty());
+
+    fs::write(&path, "port = 2\n[log]\nlevel = \"debug\"").unwrap();
+    settings.reload().unwrap();
+    fs::write(&path, "port = 2").unwrap();
+    settings.reload().unwrap();
+    assert_eq!(
+        *changes.lock().unwrap(),
+        vec![
+            (
+                "log.level".to_owned(),
+                Some("info".to_owned()),
+                Some("debug".to_owned())
+            ),
+            ("log.level".to_owned(), Some("debug".to_owned()), None),
+        ]
+    );
+
+    drop(settings);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_watch_subscribe_channel() {
+    let dir = directory("subscribe-channel");
+    let path = dir.join("Settings.toml");
+    fs::write(&path, "features = [\"a\"]").unwrap();
+
+    let settings = watch(Config::builder().add_source(File::from(path.as_path())));
+    let changes = settings.subscribe_channel("features").unwrap();
+
+    fs::write(&path, "features = [\"a\", \"b\"]").unwrap();
+    let change = changes.recv_timeout(Duration::from_secs(10)).unwrap();
+    let features: Vec<String> = change
+        .new_value()
+        .unwrap()
+        .clone()
+        .try_deserialize()
+        .unwrap();
+    assert_eq!(features, vec!["a", "b"]);
+    assert_eq!(
+        change
+            .old_value()
+            .unwrap()
+            .clone()
+            .into_array()
+            .unwrap()
+            .len(),
+        1
+    );
+
+    assert!(settings.subscribe_channel("features[").is_err());
+
+    drop(settings);
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,190 @@
+#![cfg(feature = "json")]
+
+use snapbox::{assert_data_eq, str};
+
+use config::{Config, File, FileFormat};
+
+fn sample() -> Config {
+    Config::builder()
+        .add_source(File::from_str(
+            r#"
+{
+  "debug": true,
+  "port": 8080,
+  "ratio": 0.5,
+  "name": "app",
+  "tags": ["a", "b"],
+  "place": {
+    "name": "Torre di Pisa",
+    "reviews": [1, 2, 3]
+  }
+}
+"#,
+            FileFormat::Json,
+        ))
+        .build()
+        .unwrap()
+}
+
+fn assert_round_trip(format: FileFormat) {
+    let c = sample();
+    let text = c.to_string_as(format).unwrap();
+
+    let reparsed = Config::builder()
+        .add_source(File::from_str(&text, format))
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        serde_json::to_value(&c).unwrap(),
+        serde_json::to_value(&reparsed).unwrap(),
+        "{text}"
+    );
+}
+
+#[test]
+fn test_write_json() {
+    assert_round_trip(FileFormat::Json);
+}
+
+#[test]
+#[cfg(feature = "toml")]
+fn test_write_toml() {
+    assert_round_trip(FileFormat::Toml);
+}
+
+#[test]
+#[cfg(feature = "yaml")]
+fn test_write_yaml() {
+    assert_round_trip(FileFormat::Yaml);
+}
+
+#[test]
+#[cfg(feature = "ron")]
+fn test_write_ron() {
+    assert_round_trip(FileFormat::Ron);
+}
+
+#[test]
+#[cfg(feature = "json5")]
+fn test_write_json5() {
+    assert_round_trip(FileFormat::Json5);
+}
+
+#[test]
+#[cfg(feature = "yaml")]
+fn test_write_yaml_float() {
+    let c = Config::builder()
+        .set_default(
+            "floats",
+            vec![1.0, f64::NEG_INFINITY, f64::INFINITY, f64::NAN],
+        )
+        .unwrap()
+        .build()
+        .unwrap();
+    let text = c.to_string_as(FileFormat::Yaml).unwrap();
+
+    assert_data_eq!(
+        &text,
+        str![[r#"
+---
+floats:
+  - 1.0
+  - -.inf
+  - .inf
+  - .nan
+
+"#]]
+    );
+
+    // The text reads back as the same floats
+    let floats: Vec<f64> = Config::builder()
+        .add_source(File::from_str(&text, FileFormat::Yaml))
+        .build()
+        .unwrap()
+        .get("floats")
+        .unwrap();
+    assert_eq!(floats[..3], [1.0, f64::NEG_INFINITY, f64::INFINITY]);
+    assert!(floats[3].is_nan());
+}
+
+#[test]
+#[cfg(feature = "yaml")]
+fn test_write_yaml_integer_out_of_range() {
+    let c = Config::builder()
+        .set_default("big", u64::MAX)
+        .unwrap()
+        .build()
+        .unwrap();
+    let res = c.to_string_as(FileFormat::Yaml);
+
+    assert_data_eq!(
+        res.unwrap_err().to_string(),
+        str![
+            "YAML does not support integers out of the range of i64, such as 18446744073709551615"
+        ]
+    );
+}
+
+#[test]
+#[cfg(feature = "ini")]
+fn test_write_ini() {
+    let c = Config::builder()
+        .set_default("debug", true)
+        .unwrap()
+        .set_default("place.name", "Torre di Pisa")
+        .unwrap()
+        .build()
+        .unwrap();
+    let text = c.to_string_as(FileFormat::Ini).unwrap();
+
+    assert_data_eq!(
+        text,
+        str![[r#"
+debug=true
+
+[place]
+name=Torre di Pisa
+
+"#]]
+    );
+}
+
+#[test]
+#[cfg(feature = "ini")]
+fn test_write_ini_nested() {
+    let res = sample().to_string_as(FileFormat::Ini);
+
+    assert_data_eq!(
+        res.unwrap_err().to_string(),
+        str!["INI does not support arrays"]
+    );
+}
+
+#[test]
+#[cfg(feature = "toml")]
+fn test_write_to_file() {
+    let path = std::env::temp_dir().join(format!("config-write-{}.toml", std::process::id()));
+
+    sample().write_to_file(&path).unwrap();
+    let reparsed = Config::builder()
+        .add_source(File::from(path.as_path()))
+        .build()
+        .unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(
+        serde_json::to_value(sample()).unwrap(),
+        serde_json::to_value(&reparsed).unwrap()
+    );
+}
+
+#[test]
+fn test_write_to_file_unknown_extension() {
+    let res = sample().write_to_file("Settings.unknown");
+
+    assert_data_eq!(
+        res.unwrap_err().to_string(),
+        str![[r#"configuration file "Settings.unknown" is not of a registered file format"#]]
+    );
+}